@@ -0,0 +1,227 @@
+//! Ledger of known answers, used to check solutions for regressions.
+//!
+//! Answers are recorded per day and per input, inputs being identified by the hash of their
+//! content, so several accounts' inputs can be verified side by side.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Error, Report, root_dir};
+
+/// The recorded answers of both parts for one input.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+/// Outcome of checking one part's answer against the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect { expected: String },
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => f.write_str("ok"),
+            Verdict::Incorrect { expected } => write!(f, "FAILED, expected {}", expected),
+            Verdict::Unknown => f.write_str("no recorded answer"),
+        }
+    }
+}
+
+/// Known answers, by day then by input hash.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Ledger {
+    days: BTreeMap<u8, BTreeMap<String, Answers>>,
+}
+
+impl Ledger {
+    /// The default ledger location, `answers.json` in the [`root_dir`].
+    pub fn default_path() -> PathBuf {
+        root_dir().join("answers.json")
+    }
+
+    /// Load a ledger, starting from an empty one if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => {
+                return Err(Error::Io {
+                    path: path.to_path_buf(),
+                    source: e,
+                });
+            }
+        };
+
+        serde_json::from_str(&content).map_err(|e| Error::InvalidFile {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let content = serde_json::to_string_pretty(self).expect("ledger is serializable");
+
+        fs::write(path, content + "\n").map_err(|e| Error::Io {
+            path: path.to_path_buf(),
+            source: e,
+        })
+    }
+
+    pub fn get(&self, day: u8, input_hash: &str) -> Option<&Answers> {
+        self.days.get(&day).and_then(|d| d.get(input_hash))
+    }
+
+    /// Check both answers of a report against the recorded ones.
+    pub fn verify(&self, report: &Report) -> (Verdict, Verdict) {
+        let answers = self.get(report.day, &report.input_hash);
+
+        (
+            verdict(answers.and_then(|a| a.part1.as_ref()), &report.part1),
+            verdict(answers.and_then(|a| a.part2.as_ref()), &report.part2),
+        )
+    }
+
+    /// Record the answers of a report for the parts that don't have one yet. Existing answers are
+    /// never overwritten. Returns whether anything was added.
+    pub fn record(&mut self, report: &Report) -> bool {
+        let answers = self
+            .days
+            .entry(report.day)
+            .or_default()
+            .entry(report.input_hash.clone())
+            .or_default();
+
+        let mut added = false;
+
+        for (known, actual) in [
+            (&mut answers.part1, &report.part1),
+            (&mut answers.part2, &report.part2),
+        ] {
+            if known.is_none() {
+                *known = Some(actual.clone());
+                added = true;
+            }
+        }
+
+        added
+    }
+}
+
+fn verdict(expected: Option<&String>, actual: &str) -> Verdict {
+    match expected {
+        Some(e) if e == actual => Verdict::Correct,
+        Some(e) => Verdict::Incorrect {
+            expected: e.clone(),
+        },
+        None => Verdict::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::Timings;
+    use crate::testing::TempPath;
+
+    fn report(day: u8, hash: &str, part1: &str, part2: &str) -> Report {
+        Report {
+            day,
+            input_hash: hash.to_string(),
+            part1: part1.to_string(),
+            part2: part2.to_string(),
+            timings: Timings {
+                parse: 0,
+                part1: 0,
+                part2: 0,
+            },
+        }
+    }
+
+    #[rstest]
+    fn test_verify() {
+        let mut ledger = Ledger::default();
+        ledger.record(&report(1, "abc", "1141", "6634"));
+
+        assert_eq!(
+            ledger.verify(&report(1, "abc", "1141", "6634")),
+            (Verdict::Correct, Verdict::Correct)
+        );
+        assert_eq!(
+            ledger.verify(&report(1, "abc", "1141", "42")),
+            (
+                Verdict::Correct,
+                Verdict::Incorrect {
+                    expected: "6634".to_string()
+                }
+            )
+        );
+        assert_eq!(
+            ledger.verify(&report(1, "def", "1141", "6634")),
+            (Verdict::Unknown, Verdict::Unknown)
+        );
+        assert_eq!(
+            ledger.verify(&report(2, "abc", "1141", "6634")),
+            (Verdict::Unknown, Verdict::Unknown)
+        );
+    }
+
+    #[rstest]
+    fn test_record_does_not_overwrite() {
+        let mut ledger = Ledger::default();
+
+        assert!(ledger.record(&report(1, "abc", "1", "2")));
+        assert!(!ledger.record(&report(1, "abc", "3", "4")));
+
+        assert_eq!(
+            ledger.get(1, "abc"),
+            Some(&Answers {
+                part1: Some("1".to_string()),
+                part2: Some("2".to_string()),
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_round_trip() {
+        let temp = TempPath::new("answers.json");
+        let path = temp.path();
+
+        assert_eq!(Ledger::load(path).unwrap(), Ledger::default());
+
+        let mut ledger = Ledger::default();
+        ledger.record(&report(1, "abc", "1141", "6634"));
+        ledger.record(&report(7, "def", "1585", "16716444407407"));
+        ledger.save(path).unwrap();
+
+        let loaded = Ledger::load(path);
+        let content = fs::read_to_string(path).unwrap();
+
+        assert_eq!(loaded.unwrap(), ledger);
+        assert!(content.contains("\"1585\""));
+    }
+
+    #[rstest]
+    fn test_load_partial_answers() {
+        let ledger: Ledger = serde_json::from_str(r#"{"3": {"abc": {"part1": "357"}}}"#).unwrap();
+
+        assert_eq!(
+            ledger.verify(&report(3, "abc", "357", "3121910778619")),
+            (Verdict::Correct, Verdict::Unknown)
+        );
+    }
+}
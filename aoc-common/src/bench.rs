@@ -0,0 +1,417 @@
+//! Repeated measurements of solutions, to get timings that can be compared across runs.
+//!
+//! Results can be kept in a [`History`], keyed by git commit, to find out when a change makes a
+//! solution slower.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Error, format_duration, root_dir};
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    /// Runs done before measuring, to warm up caches and branch predictors.
+    pub warmup: usize,
+    /// Measured runs.
+    pub iterations: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 1,
+            iterations: 10,
+        }
+    }
+}
+
+/// Summary of the durations of a stage over all the measured runs, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min: u128,
+    pub median: u128,
+    pub mean: u128,
+    pub stddev: u128,
+}
+
+impl Stats {
+    /// # Panics
+    ///
+    /// Panics if there are no samples.
+    pub fn from_samples(samples: &[u128]) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let mean = sorted.iter().sum::<u128>() as f64 / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|&s| (s as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Self {
+            min: sorted[0],
+            median,
+            mean: mean.round() as u128,
+            stddev: variance.sqrt().round() as u128,
+        }
+    }
+}
+
+/// Timings of every stage of a solution over repeated runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub day: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl BenchReport {
+    /// The stages of the report, with their names.
+    pub fn stages(&self) -> [(&'static str, &Stats); 3] {
+        [
+            ("parse", &self.parse),
+            ("part1", &self.part1),
+            ("part2", &self.part2),
+        ]
+    }
+
+    /// Render the report as an aligned table, with a row per stage.
+    pub fn to_table(&self) -> String {
+        let mut table = format!(
+            "{:<8}{:>12}{:>12}{:>12}{:>12}\n",
+            "", "min", "median", "mean", "stddev"
+        );
+
+        for (name, s) in self.stages() {
+            table.push_str(&format!(
+                "{:<8}{:>12}{:>12}{:>12}{:>12}\n",
+                name,
+                format_duration(s.min),
+                format_duration(s.median),
+                format_duration(s.mean),
+                format_duration(s.stddev),
+            ));
+        }
+
+        table
+    }
+}
+
+/// A bench report recorded in the history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub commit: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub report: BenchReport,
+}
+
+/// Bench results of previous runs, stored as one JSON document per line.
+#[derive(Debug, Clone)]
+pub struct History {
+    path: PathBuf,
+    entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// The default history location, `bench-history.jsonl` in the [`root_dir`].
+    pub fn default_path() -> PathBuf {
+        root_dir().join("bench-history.jsonl")
+    }
+
+    /// Load the history, starting from an empty one if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(Error::Io {
+                    path: path.to_path_buf(),
+                    source: e,
+                });
+            }
+        };
+
+        let entries = content
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| {
+                serde_json::from_str(l).map_err(|e| Error::InvalidFile {
+                    path: path.to_path_buf(),
+                    message: format!("line {}: {}", i + 1, e),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    /// Record reports for a commit, appending them to the history file.
+    pub fn append(&mut self, commit: &str, reports: &[BenchReport]) -> Result<(), Error> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let entries = reports
+            .iter()
+            .map(|r| HistoryEntry {
+                commit: commit.to_string(),
+                timestamp,
+                report: r.clone(),
+            })
+            .collect::<Vec<HistoryEntry>>();
+
+        let io_err = |e| Error::Io {
+            path: self.path.clone(),
+            source: e,
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(io_err)?;
+
+        for e in &entries {
+            let line = serde_json::to_string(e).expect("entries are serializable");
+            writeln!(file, "{}", line).map_err(io_err)?;
+        }
+
+        self.entries.extend(entries);
+
+        Ok(())
+    }
+
+    /// The most recent entry of a day to compare a run of `current` against: the latest one of
+    /// `commit` if given (matching by prefix), otherwise the latest one of any other commit.
+    pub fn baseline(&self, day: u8, current: &str, commit: Option<&str>) -> Option<&HistoryEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .filter(|e| e.report.day == day)
+            .find(|e| match commit {
+                Some(c) => e.commit.starts_with(c),
+                None => e.commit != current,
+            })
+    }
+}
+
+/// The current git commit, suffixed with `-dirty` if the working tree has uncommitted changes.
+/// Returns `None` outside of a git repository.
+pub fn current_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    };
+
+    let commit = git(&["rev-parse", "--short", "HEAD"])?;
+    let dirty =
+        git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|s| !s.is_empty());
+
+    if dirty {
+        Some(format!("{}-dirty", commit))
+    } else {
+        Some(commit)
+    }
+}
+
+/// Change of the median duration of a stage between two bench runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub stage: &'static str,
+    pub baseline: u128,
+    pub current: u128,
+}
+
+impl Change {
+    /// Relative change, in percent. Positive when the current run is slower.
+    pub fn percent(&self) -> f64 {
+        if self.baseline == 0 {
+            return 0.0;
+        }
+
+        (self.current as f64 - self.baseline as f64) * 100.0 / self.baseline as f64
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent() > threshold
+    }
+}
+
+/// Compare the median durations of every stage of two reports of the same day.
+pub fn compare(baseline: &BenchReport, current: &BenchReport) -> Vec<Change> {
+    baseline
+        .stages()
+        .into_iter()
+        .zip(current.stages())
+        .map(|((stage, b), (_, c))| Change {
+            stage,
+            baseline: b.median,
+            current: c.median,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::testing::TempPath;
+
+    #[rstest]
+    #[case(vec![5], Stats { min: 5, median: 5, mean: 5, stddev: 0 })]
+    #[case(vec![3, 1, 2], Stats { min: 1, median: 2, mean: 2, stddev: 1 })]
+    #[case(vec![4, 1, 3, 2], Stats { min: 1, median: 2, mean: 3, stddev: 1 })]
+    #[case(vec![2, 4, 4, 4, 5, 5, 7, 9], Stats { min: 2, median: 4, mean: 5, stddev: 2 })]
+    #[case(vec![1000, 1000, 4000], Stats { min: 1000, median: 1000, mean: 2000, stddev: 1414 })]
+    fn test_stats(#[case] samples: Vec<u128>, #[case] expected: Stats) {
+        assert_eq!(Stats::from_samples(&samples), expected);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "no samples")]
+    fn test_stats_without_samples() {
+        Stats::from_samples(&[]);
+    }
+
+    #[rstest]
+    fn test_to_table() {
+        let stats = Stats {
+            min: 1000,
+            median: 1500,
+            mean: 2000,
+            stddev: 250,
+        };
+        let report = BenchReport {
+            day: 1,
+            iterations: 10,
+            parse: stats,
+            part1: stats,
+            part2: stats,
+        };
+
+        let table = report.to_table();
+        let lines = table.lines().collect::<Vec<&str>>();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[1],
+            "parse        1.000μs     1.500μs     2.000μs     0.250μs"
+        );
+        assert!(lines[3].starts_with("part2"));
+    }
+
+    fn report(day: u8, median: u128) -> BenchReport {
+        let stats = Stats {
+            min: median,
+            median,
+            mean: median,
+            stddev: 0,
+        };
+
+        BenchReport {
+            day,
+            iterations: 10,
+            parse: stats,
+            part1: stats,
+            part2: stats,
+        }
+    }
+
+    #[rstest]
+    #[case(1000, 1000, 0.0, false)]
+    #[case(1000, 1100, 10.0, false)]
+    #[case(1000, 1101, 10.1, true)]
+    #[case(1000, 500, -50.0, false)]
+    #[case(0, 500, 0.0, false)]
+    fn test_change(
+        #[case] baseline: u128,
+        #[case] current: u128,
+        #[case] percent: f64,
+        #[case] regression: bool,
+    ) {
+        let change = Change {
+            stage: "part1",
+            baseline,
+            current,
+        };
+
+        assert!((change.percent() - percent).abs() < 1e-9);
+        assert_eq!(change.is_regression(10.0), regression);
+    }
+
+    #[rstest]
+    fn test_compare() {
+        let mut current = report(1, 1000);
+        current.part2.median = 3000;
+
+        let changes = compare(&report(1, 1000), &current);
+
+        assert_eq!(
+            changes
+                .iter()
+                .map(|c| (c.stage, c.is_regression(10.0)))
+                .collect::<Vec<_>>(),
+            vec![("parse", false), ("part1", false), ("part2", true)]
+        );
+    }
+
+    #[rstest]
+    fn test_history() {
+        let temp = TempPath::new("history.jsonl");
+
+        let mut history = History::load(temp.path()).unwrap();
+        history
+            .append("aaa111", &[report(1, 100), report(2, 200)])
+            .unwrap();
+        history.append("bbb222", &[report(1, 110)]).unwrap();
+        history.append("ccc333", &[report(1, 120)]).unwrap();
+
+        let loaded = History::load(temp.path());
+
+        let history = loaded.unwrap();
+        assert_eq!(history.entries().len(), 4);
+
+        let median = |e: Option<&HistoryEntry>| e.map(|e| e.report.part1.median);
+
+        assert_eq!(median(history.baseline(1, "ccc333", None)), Some(110));
+        assert_eq!(median(history.baseline(1, "ddd444", None)), Some(120));
+        assert_eq!(
+            median(history.baseline(1, "ddd444", Some("aaa"))),
+            Some(100)
+        );
+        assert_eq!(median(history.baseline(2, "aaa111", None)), None);
+        assert_eq!(median(history.baseline(3, "ddd444", None)), None);
+    }
+}
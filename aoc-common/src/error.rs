@@ -0,0 +1,65 @@
+use std::fmt::Display;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The input file doesn't exist, most likely because it hasn't been downloaded yet.
+    MissingInput(PathBuf),
+    /// The input file exists but couldn't be read.
+    Io { path: PathBuf, source: io::Error },
+    /// A line of the input couldn't be parsed. Lines are numbered from 1.
+    Parse {
+        line: usize,
+        text: String,
+        message: String,
+    },
+    /// The input was read but rejected by a solution's parser.
+    InvalidInput(String),
+    /// A data file, such as the answers ledger, has invalid content.
+    InvalidFile { path: PathBuf, message: String },
+    /// A file couldn't be downloaded.
+    Fetch { url: String, message: String },
+}
+
+impl Error {
+    pub(crate) fn io(path: PathBuf, source: io::Error) -> Self {
+        if source.kind() == io::ErrorKind::NotFound {
+            Error::MissingInput(path)
+        } else {
+            Error::Io { path, source }
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingInput(path) => {
+                write!(f, "Input not downloaded: {} not found", path.display())
+            }
+            Error::Io { path, source } => {
+                write!(f, "Unable to read {}: {}", path.display(), source)
+            }
+            Error::Parse {
+                line,
+                text,
+                message,
+            } => write!(f, "Invalid value on line {}: {:?}: {}", line, text, message),
+            Error::InvalidInput(message) => write!(f, "Invalid input: {}", message),
+            Error::InvalidFile { path, message } => {
+                write!(f, "Invalid file {}: {}", path.display(), message)
+            }
+            Error::Fetch { url, message } => write!(f, "Unable to download {}: {}", url, message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,621 @@
+//! Download puzzle inputs and examples from the Advent of Code website.
+//!
+//! Downloaded files are kept in a content-addressed [`Cache`], so a day is only ever requested
+//! once, and requests are throttled to a minimum interval to stay polite with the servers.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread::sleep;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use itertools::Itertools;
+use sha2::{Digest, Sha256};
+use tracing::{debug, info};
+
+use super::{Error, input_dir};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2025;
+
+/// Minimum delay between two requests to the server.
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = "github.com/mathieu-lemay/aoc-2025 by acidrain1@gmail.com";
+
+/// The SHA-256 of a file's content, as a lowercase hex string.
+pub fn content_hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Content-addressed store for downloaded files.
+///
+/// Contents are stored once under `objects/<hash>`, and named references under `refs/<name>` list
+/// the hashes of the contents they point to, one per line.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The default cache location, `.cache` in the input directory.
+    pub fn default_dir() -> PathBuf {
+        input_dir().join(".cache")
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The contents pointed to by a reference, or `None` if it isn't cached.
+    pub fn get(&self, name: &str) -> Result<Option<Vec<String>>, Error> {
+        let path = self.ref_path(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let hashes = read(&path)?;
+
+        hashes
+            .lines()
+            .map(|h| self.object_path(h))
+            .map(|p| {
+                if p.exists() {
+                    read(&p).map(Some)
+                } else {
+                    Ok(None)
+                }
+            })
+            .collect::<Result<Option<Vec<String>>, Error>>()
+    }
+
+    /// Store contents and point a reference to them, returning their hashes.
+    pub fn put(&self, name: &str, contents: &[String]) -> Result<Vec<String>, Error> {
+        let hashes = contents
+            .iter()
+            .map(|c| {
+                let hash = content_hash(c);
+                let path = self.object_path(&hash);
+                if !path.exists() {
+                    write(&path, c)?;
+                }
+
+                Ok(hash)
+            })
+            .collect::<Result<Vec<String>, Error>>()?;
+
+        write(&self.ref_path(name), &hashes.iter().join("\n"))?;
+
+        Ok(hashes)
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.dir.join("objects").join(hash)
+    }
+
+    fn ref_path(&self, name: &str) -> PathBuf {
+        self.dir.join("refs").join(name)
+    }
+}
+
+fn read(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|e| Error::Io {
+        path: path.to_path_buf(),
+        source: e,
+    })
+}
+
+/// Write a file atomically, creating its parent directories if needed.
+fn write(path: &Path, content: &str) -> Result<(), Error> {
+    let io_err = |e| Error::Io {
+        path: path.to_path_buf(),
+        source: e,
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(io_err)?;
+    }
+
+    let tmp = temp_path(path);
+    fs::write(&tmp, content).map_err(io_err)?;
+    fs::rename(&tmp, path).map_err(io_err)
+}
+
+/// Where a file is written before being moved in place. The whole name is kept, since refs only
+/// differ by their extension, and the process id keeps concurrent runs apart.
+fn temp_path(path: &Path) -> PathBuf {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", std::process::id()));
+
+    tmp.into()
+}
+
+/// A file saved by [`Fetcher::save`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Saved {
+    Written(PathBuf),
+    /// The file was already there and was kept.
+    Exists(PathBuf),
+}
+
+/// Files returned by the [`Fetcher`], and whether they came from the cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fetched {
+    pub contents: Vec<String>,
+    pub from_cache: bool,
+}
+
+pub struct Fetcher {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    cache: Cache,
+    min_interval: Duration,
+}
+
+impl Fetcher {
+    /// A fetcher for the Advent of Code website, authenticated with the `session` cookie of a
+    /// logged-in user, and using the default cache.
+    pub fn new(session: &str) -> Self {
+        Self {
+            agent: ureq::Agent::new_with_defaults(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: session.to_string(),
+            cache: Cache::new(Cache::default_dir()),
+            min_interval: DEFAULT_MIN_INTERVAL,
+        }
+    }
+
+    /// Send requests to another server, such as a local stand-in.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// The puzzle input of a day. The input is only downloaded if it isn't already cached.
+    pub fn input(&self, day: u8) -> Result<Fetched, Error> {
+        self.fetch(&format!("day{:02}.input", day), false, || {
+            self.get(&format!("/{}/day/{}/input", YEAR, day))
+                .map(|i| vec![i])
+        })
+    }
+
+    /// The example blocks of a day's puzzle description. The description is only downloaded if
+    /// its examples aren't already cached.
+    pub fn examples(&self, day: u8) -> Result<Fetched, Error> {
+        self.fetch_examples(day, false)
+    }
+
+    /// The example blocks of a day's puzzle description, downloaded again even if they are
+    /// cached. The examples of part 2 are only in the description once part 1 is solved.
+    pub fn refresh_examples(&self, day: u8) -> Result<Fetched, Error> {
+        self.fetch_examples(day, true)
+    }
+
+    /// Save the input of a day as `dayXX.txt` in `dir`, and its examples as `dayXX-example-N.txt`
+    /// with `examples`. Files that already exist are kept without requesting anything, unless
+    /// `refresh` is set, in which case the examples are also downloaded again.
+    pub fn save(
+        &self,
+        day: u8,
+        dir: &Path,
+        examples: bool,
+        refresh: bool,
+    ) -> Result<Vec<Saved>, Error> {
+        let mut saved = Vec::new();
+
+        let input = dir.join(format!("day{:02}.txt", day));
+        if input.exists() && !refresh {
+            saved.push(Saved::Exists(input));
+        } else {
+            write(&input, &self.input(day)?.contents.concat())?;
+            saved.push(Saved::Written(input));
+        }
+
+        let example = |i: usize| dir.join(format!("day{:02}-example-{}.txt", day, i + 1));
+        if !examples {
+            return Ok(saved);
+        }
+        if example(0).exists() && !refresh {
+            saved.push(Saved::Exists(example(0)));
+            return Ok(saved);
+        }
+
+        for (i, e) in self
+            .fetch_examples(day, refresh)?
+            .contents
+            .iter()
+            .enumerate()
+        {
+            write(&example(i), e)?;
+            saved.push(Saved::Written(example(i)));
+        }
+
+        Ok(saved)
+    }
+
+    fn fetch_examples(&self, day: u8, refresh: bool) -> Result<Fetched, Error> {
+        self.fetch(&format!("day{:02}.examples", day), refresh, || {
+            self.get(&format!("/{}/day/{}", YEAR, day))
+                .map(|page| extract_examples(&page))
+        })
+    }
+
+    fn fetch(
+        &self,
+        name: &str,
+        refresh: bool,
+        download: impl FnOnce() -> Result<Vec<String>, Error>,
+    ) -> Result<Fetched, Error> {
+        if !refresh && let Some(contents) = self.cache.get(name)? {
+            debug!("{} found in cache, not downloading it again", name);
+            return Ok(Fetched {
+                contents,
+                from_cache: true,
+            });
+        }
+
+        let contents = download()?;
+        self.cache.put(name, &contents)?;
+
+        Ok(Fetched {
+            contents,
+            from_cache: false,
+        })
+    }
+
+    fn get(&self, path: &str) -> Result<String, Error> {
+        self.throttle()?;
+
+        let url = format!("{}{}", self.base_url, path);
+        info!("Downloading {}", url);
+
+        self.agent
+            .get(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", USER_AGENT)
+            .call()
+            .and_then(|mut r| r.body_mut().read_to_string())
+            .map_err(|e| Error::Fetch {
+                url,
+                message: e.to_string(),
+            })
+    }
+
+    /// Wait until at least `min_interval` has passed since the last request. The time of the last
+    /// request is kept in the cache so the limit also applies across runs.
+    fn throttle(&self) -> Result<(), Error> {
+        let path = self.cache.dir().join("last-request");
+
+        let last = fs::read_to_string(&path)
+            .ok()
+            .and_then(|t| t.trim().parse::<u64>().ok())
+            .map(|t| UNIX_EPOCH + Duration::from_millis(t));
+
+        if let Some(elapsed) = last.and_then(|t| SystemTime::now().duration_since(t).ok())
+            && elapsed < self.min_interval
+        {
+            let wait = self.min_interval - elapsed;
+            debug!("Waiting {:?} before the next request", wait);
+            sleep(wait);
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        write(&path, &now.to_string())
+    }
+}
+
+/// Extract the content of the `<pre><code>` blocks of a puzzle description.
+fn extract_examples(page: &str) -> Vec<String> {
+    page.split("<pre><code>")
+        .skip(1)
+        .filter_map(|b| b.split_once("</code></pre>"))
+        .map(|(code, _)| unescape(&strip_tags(code)))
+        .collect()
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Instant;
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::testing::TempPath;
+
+    /// A local stand-in for the Advent of Code server, answering each request with the next
+    /// response and recording the request heads.
+    struct MockServer {
+        url: String,
+        requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl MockServer {
+        fn start(responses: Vec<(u16, &'static str)>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+
+            let recorded = requests.clone();
+            thread::spawn(move || {
+                for (status, body) in responses {
+                    let (mut stream, _) = listener.accept().unwrap();
+
+                    let mut head = String::new();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if line.trim().is_empty() {
+                            break;
+                        }
+                        head.push_str(&line);
+                    }
+                    recorded.lock().unwrap().push(head);
+
+                    write!(
+                        stream,
+                        "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    )
+                    .unwrap();
+                }
+            });
+
+            Self { url, requests }
+        }
+
+        fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn fetcher(server: &MockServer, cache: &Cache) -> Fetcher {
+        Fetcher::new("s3cr3t")
+            .with_base_url(&server.url)
+            .with_cache(cache.clone())
+            .with_min_interval(Duration::ZERO)
+    }
+
+    #[rstest]
+    fn test_content_hash() {
+        assert_eq!(
+            content_hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[rstest]
+    fn test_cache_round_trip() {
+        let dir = TempPath::new("cache-round-trip");
+        let cache = Cache::new(dir.path());
+
+        assert_eq!(cache.get("day01.input").unwrap(), None);
+
+        let contents = vec!["a".to_string(), "b".to_string(), "a".to_string()];
+        let hashes = cache.put("day01.examples", &contents).unwrap();
+
+        assert_eq!(hashes.len(), 3);
+        assert_eq!(hashes[0], hashes[2]);
+        assert_eq!(cache.get("day01.examples").unwrap(), Some(contents));
+        assert_eq!(
+            fs::read_dir(cache.dir().join("objects")).unwrap().count(),
+            2
+        );
+    }
+
+    #[rstest]
+    fn test_temp_path() {
+        let input = temp_path(Path::new("refs/day01.input"));
+        let examples = temp_path(Path::new("refs/day01.examples"));
+
+        assert_ne!(input, examples);
+        assert_eq!(
+            input,
+            PathBuf::from(format!("refs/day01.input.{}.tmp", std::process::id()))
+        );
+    }
+
+    #[rstest]
+    fn test_extract_examples() {
+        let page = "<p>For example:</p>\n<pre><code>a -&gt; b\n<em>c</em> &amp; d\n</code></pre>\n\
+                    <p>Then:</p><pre><code>1,2\n</code></pre>";
+
+        assert_eq!(extract_examples(page), vec!["a -> b\nc & d\n", "1,2\n"]);
+    }
+
+    #[rstest]
+    fn test_fetch_input_is_cached() {
+        let server = MockServer::start(vec![(200, "1\n2\n3\n")]);
+        let dir = TempPath::new("cache-input");
+        let cache = Cache::new(dir.path());
+        let fetcher = fetcher(&server, &cache);
+
+        let first = fetcher.input(7).unwrap();
+        let second = fetcher.input(7).unwrap();
+
+        assert_eq!(first.contents, vec!["1\n2\n3\n"]);
+        assert!(!first.from_cache);
+        assert_eq!(second.contents, first.contents);
+        assert!(second.from_cache);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2025/day/7/input HTTP/1.1"));
+        assert!(requests[0].contains("session=s3cr3t"));
+    }
+
+    #[rstest]
+    fn test_fetch_examples() {
+        let server = MockServer::start(vec![(200, "<pre><code>L68\nL30\n</code></pre>")]);
+        let dir = TempPath::new("cache-examples");
+        let cache = Cache::new(dir.path());
+        let fetcher = fetcher(&server, &cache);
+
+        let examples = fetcher.examples(1).unwrap();
+
+        assert_eq!(examples.contents, vec!["L68\nL30\n"]);
+        assert!(server.requests()[0].starts_with("GET /2025/day/1 HTTP/1.1"));
+    }
+
+    #[rstest]
+    fn test_refresh_examples() {
+        let server = MockServer::start(vec![
+            (200, "<pre><code>1\n</code></pre>"),
+            (
+                200,
+                "<pre><code>1\n</code></pre><pre><code>2\n</code></pre>",
+            ),
+        ]);
+        let dir = TempPath::new("cache-refresh");
+        let cache = Cache::new(dir.path());
+        let fetcher = fetcher(&server, &cache);
+
+        assert_eq!(fetcher.examples(5).unwrap().contents, vec!["1\n"]);
+        assert!(fetcher.examples(5).unwrap().from_cache);
+
+        let refreshed = fetcher.refresh_examples(5).unwrap();
+        assert_eq!(refreshed.contents, vec!["1\n", "2\n"]);
+        assert!(!refreshed.from_cache);
+        assert_eq!(fetcher.examples(5).unwrap().contents, refreshed.contents);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[rstest]
+    fn test_save() {
+        let server = MockServer::start(vec![(200, "1\n2\n"), (200, "<pre><code>3\n</code></pre>")]);
+        let dir = TempPath::new("save");
+        let cache = Cache::new(dir.path().join(".cache"));
+        let fetcher = fetcher(&server, &cache);
+
+        let saved = fetcher.save(4, dir.path(), true, false).unwrap();
+
+        let input = dir.path().join("day04.txt");
+        let example = dir.path().join("day04-example-1.txt");
+        assert_eq!(
+            saved,
+            vec![
+                Saved::Written(input.clone()),
+                Saved::Written(example.clone())
+            ]
+        );
+        assert_eq!(fs::read_to_string(&input).unwrap(), "1\n2\n");
+        assert_eq!(fs::read_to_string(&example).unwrap(), "3\n");
+    }
+
+    #[rstest]
+    fn test_save_existing_files_without_requests() {
+        let server = MockServer::start(vec![]);
+        let dir = TempPath::new("save-existing");
+        let cache = Cache::new(dir.path().join(".cache"));
+        let fetcher = fetcher(&server, &cache);
+
+        // Saved by other means, with nothing in the cache
+        let input = dir.path().join("day04.txt");
+        let example = dir.path().join("day04-example-1.txt");
+        write(&input, "1\n").unwrap();
+        write(&example, "2\n").unwrap();
+
+        let saved = fetcher.save(4, dir.path(), true, false).unwrap();
+
+        assert_eq!(saved, vec![Saved::Exists(input), Saved::Exists(example)]);
+        assert!(server.requests().is_empty());
+    }
+
+    #[rstest]
+    fn test_save_refresh() {
+        let server = MockServer::start(vec![(200, "1\n"), (200, "<pre><code>3\n</code></pre>")]);
+        let dir = TempPath::new("save-refresh");
+        let cache = Cache::new(dir.path().join(".cache"));
+        let fetcher = fetcher(&server, &cache);
+
+        let input = dir.path().join("day04.txt");
+        let example = dir.path().join("day04-example-1.txt");
+        write(&input, "old\n").unwrap();
+        write(&example, "old\n").unwrap();
+
+        fetcher.save(4, dir.path(), true, true).unwrap();
+
+        assert_eq!(fs::read_to_string(&input).unwrap(), "1\n");
+        assert_eq!(fs::read_to_string(&example).unwrap(), "3\n");
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[rstest]
+    fn test_fetch_error_is_not_cached() {
+        let server = MockServer::start(vec![(404, "Not found"), (200, "42\n")]);
+        let dir = TempPath::new("cache-error");
+        let cache = Cache::new(dir.path());
+        let fetcher = fetcher(&server, &cache);
+
+        let err = fetcher.input(3).unwrap_err();
+        assert!(matches!(err, Error::Fetch { url, .. } if url.ends_with("/2025/day/3/input")));
+
+        assert_eq!(fetcher.input(3).unwrap().contents, vec!["42\n"]);
+    }
+
+    #[rstest]
+    fn test_fetch_is_throttled() {
+        let server = MockServer::start(vec![(200, "1\n"), (200, "2\n")]);
+        let dir = TempPath::new("cache-throttle");
+        let cache = Cache::new(dir.path());
+        let fetcher = fetcher(&server, &cache).with_min_interval(Duration::from_millis(300));
+
+        let start = Instant::now();
+        fetcher.input(1).unwrap();
+        fetcher.input(2).unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        assert_eq!(server.requests().len(), 2);
+    }
+}
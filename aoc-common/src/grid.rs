@@ -1,6 +1,47 @@
-use std::fmt::Display;
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops::{Index, IndexMut};
 
-use super::Point;
+pub use self::automaton::{Automaton, Cycle, Update};
+pub use self::parse::{GridParser, Markers};
+pub use self::regions::{Connectivity, Label, Region, Regions};
+pub use self::sparse::SparseGrid;
+use super::{Direction, Point};
+
+mod automaton;
+mod parse;
+mod regions;
+mod sparse;
+
+/// Operations shared by the grid types, for algorithms that can run on any of them.
+pub trait GridLike {
+    type Value;
+
+    /// How cells are located: `Point<usize>` on a [`Grid`], `Point<i64>` on a [`SparseGrid`].
+    type Position: Copy + Eq + Hash + Debug;
+
+    /// The value of a cell, if it is in the grid and set.
+    fn value(&self, pos: &Self::Position) -> Option<&Self::Value>;
+
+    fn set_value(&mut self, pos: &Self::Position, value: Self::Value);
+
+    /// The position next to a cell in a direction, if there can be a cell there.
+    fn step(&self, pos: &Self::Position, direction: Direction) -> Option<Self::Position>;
+
+    /// The positions of every cell with a value.
+    fn positions(&self) -> impl Iterator<Item = Self::Position>;
+
+    /// The positions next to a cell in the given directions.
+    fn adjacent<'a>(
+        &'a self,
+        pos: &Self::Position,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = Self::Position> + use<'a, Self> {
+        let pos = *pos;
+
+        directions.iter().filter_map(move |&d| self.step(&pos, d))
+    }
+}
 
 #[derive(PartialEq, Clone)]
 pub struct GridCell<T> {
@@ -8,191 +49,348 @@ pub struct GridCell<T> {
     pub value: T,
 }
 
+/// A rectangle of cells, from its top left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// What lies past the edges of a grid, for neighbor lookups and steps.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Boundary<T> {
+    /// Nothing, the grid has hard edges.
+    Clamped,
+    /// The opposite side of the grid, which is a torus.
+    Wrapping,
+    /// Cells with a value, but no position in the grid.
+    Padded(T),
+}
+
+/// A rectangular grid, stored row by row in a single buffer.
 #[derive(PartialEq, Clone)]
 pub struct Grid<T> {
     pub height: usize,
     pub width: usize,
-    values: Vec<Vec<T>>,
+    values: Vec<T>,
+    boundary: Boundary<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            height,
+            width,
+            values: vec![value; width * height],
+            boundary: Boundary::Clamped,
+        }
+    }
+
+    pub fn with_boundary(mut self, boundary: Boundary<T>) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
+    pub fn boundary(&self) -> &Boundary<T> {
+        &self.boundary
+    }
+
+    /// The position of a cell in the flat buffer of values.
+    fn offset(&self, x: usize, y: usize) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "{},{} out of the grid",
+            x,
+            y
+        );
+        y * self.width + x
+    }
+
+    pub fn contains(&self, pos: &Point<usize>) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    /// The position next to a cell in a direction. Past the edges, it is on the other side of the
+    /// grid if it wraps around, otherwise there is none.
+    pub fn step(&self, pos: &Point<usize>, direction: Direction) -> Option<Point<usize>> {
+        match self.boundary {
+            Boundary::Wrapping if self.contains(pos) => {
+                Some(pos.wrapping_step(direction, self.width, self.height))
+            }
+            _ => pos.checked_step(direction).filter(|p| self.contains(p)),
+        }
+    }
+
+    /// The values of every cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.values.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.values.iter_mut()
+    }
+
+    /// The position of every cell, row by row. The iterator doesn't borrow the grid, so cells can
+    /// be modified while iterating.
+    pub fn positions(&self) -> impl Iterator<Item = Point<usize>> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `y` is out of the grid.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {} out of the grid", y);
+        &self.values[y * self.width..(y + 1) * self.width]
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `x` is out of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of the grid", x);
+        self.values.iter().skip(x).step_by(self.width)
+    }
+
+    /// The position of the first cell with `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Point<usize>>
+    where
+        T: PartialEq,
+    {
+        self.values
+            .iter()
+            .position(|v| v == value)
+            .map(|i| Point::new(i % self.width, i / self.width))
+    }
+
+    /// A grid of the same size, with `f` applied to the value of every cell.
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        let boundary = match &self.boundary {
+            Boundary::Clamped => Boundary::Clamped,
+            Boundary::Wrapping => Boundary::Wrapping,
+            Boundary::Padded(v) => Boundary::Padded(f(v)),
+        };
+
+        Grid {
+            height: self.height,
+            width: self.width,
+            values: self.values.iter().map(f).collect(),
+            boundary,
+        }
+    }
+
+    /// A borrowed part of the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rectangle isn't fully in the grid.
+    pub fn view(&self, rect: Rect) -> GridView<'_, T> {
+        assert!(
+            rect.x + rect.width <= self.width && rect.y + rect.height <= self.height,
+            "{:?} out of the grid",
+            rect
+        );
+
+        GridView { grid: self, rect }
+    }
 }
 
 impl<T> Grid<T>
 where
     T: Copy,
 {
-    pub fn get(&self, pos: &Point<usize>) -> T {
-        self.values[pos.y][pos.x]
+    /// Every cell with its position, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = GridCell<T>> {
+        self.positions()
+            .zip(self.iter())
+            .map(|(position, &value)| GridCell { position, value })
     }
 
-    pub fn get_above(&self, pos: &Point<usize>) -> Option<T> {
-        if pos.y > 0 {
-            Some(self.values[pos.y - 1][pos.x])
-        } else {
-            None
+    /// The value of a cell, or `None` if it is out of the grid.
+    pub fn get(&self, pos: &Point<usize>) -> Option<T> {
+        self.contains(pos).then(|| self[*pos])
+    }
+
+    /// The value next to a cell in a direction, following the boundary of the grid.
+    pub fn get_towards(&self, pos: &Point<usize>, direction: Direction) -> Option<T> {
+        match (self.step(pos, direction), self.boundary) {
+            (Some(p), _) => Some(self[p]),
+            (None, Boundary::Padded(v)) => Some(v),
+            (None, _) => None,
         }
     }
 
+    pub fn get_above(&self, pos: &Point<usize>) -> Option<T> {
+        self.get_towards(pos, Direction::North)
+    }
+
     pub fn get_below(&self, pos: &Point<usize>) -> Option<T> {
-        if pos.y < self.height - 1 {
-            Some(self.values[pos.y + 1][pos.x])
-        } else {
-            None
-        }
+        self.get_towards(pos, Direction::South)
     }
 
     pub fn get_left(&self, pos: &Point<usize>) -> Option<T> {
-        if pos.x > 0 {
-            Some(self.values[pos.y][pos.x - 1])
-        } else {
-            None
-        }
+        self.get_towards(pos, Direction::West)
     }
 
     pub fn get_right(&self, pos: &Point<usize>) -> Option<T> {
-        if pos.x < self.width - 1 {
-            Some(self.values[pos.y][pos.x + 1])
-        } else {
-            None
-        }
+        self.get_towards(pos, Direction::East)
     }
 
     pub fn set(&mut self, pos: &Point<usize>, value: T) {
-        self.values[pos.y][pos.x] = value
+        self[*pos] = value
     }
 
+    /// All 8 neighbors of a cell, row by row. Prefer [`Grid::neighbors8`], which doesn't
+    /// allocate.
     pub fn get_neighbors(&self, pos: &Point<usize>) -> Vec<GridCell<T>> {
-        let mut neighbors = Vec::with_capacity(8);
-
-        if pos.y > 0 {
-            let y = pos.y - 1;
-
-            // North West
-            if pos.x > 0 {
-                let x = pos.x - 1;
-                let v = self.values[y][x];
-
-                neighbors.push(GridCell {
-                    position: Point::new(x, y),
-                    value: v,
-                })
-            }
-
-            // North
-            {
-                let v = self.values[y][pos.x];
+        const ORDER: [Direction; 8] = [
+            Direction::NorthWest,
+            Direction::North,
+            Direction::NorthEast,
+            Direction::West,
+            Direction::East,
+            Direction::SouthWest,
+            Direction::South,
+            Direction::SouthEast,
+        ];
+
+        self.neighbors(pos, &ORDER).collect()
+    }
 
-                neighbors.push(GridCell {
-                    position: Point::new(pos.x, y),
-                    value: v,
-                })
-            }
+    /// The neighbors of a cell in the given directions, following the boundary of the grid.
+    /// Padding cells have no position, and are skipped, see [`Grid::neighbor_values`].
+    pub fn neighbors<'a>(
+        &'a self,
+        pos: &Point<usize>,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = GridCell<T>> + 'a {
+        let pos = *pos;
+
+        directions.iter().filter_map(move |&d| {
+            let position = self.step(&pos, d)?;
+
+            Some(GridCell {
+                position,
+                value: self[position],
+            })
+        })
+    }
 
-            // North East
-            if pos.x < self.width - 1 {
-                let x = pos.x + 1;
-                let v = self.values[y][x];
+    /// The neighbors of a cell above, below, left and right of it.
+    pub fn neighbors4(&self, pos: &Point<usize>) -> impl Iterator<Item = GridCell<T>> + '_ {
+        self.neighbors(pos, &Direction::CARDINAL)
+    }
 
-                neighbors.push(GridCell {
-                    position: Point::new(x, y),
-                    value: v,
-                })
-            }
-        }
+    /// The neighbors of a cell, including diagonals.
+    pub fn neighbors8(&self, pos: &Point<usize>) -> impl Iterator<Item = GridCell<T>> + '_ {
+        self.neighbors(pos, &Direction::ALL)
+    }
 
-        // West
-        if pos.x > 0 {
-            let x = pos.x - 1;
-            let v = self.values[pos.y][x];
+    /// The values of the neighbors of a cell in the given directions, including padding cells.
+    pub fn neighbor_values<'a>(
+        &'a self,
+        pos: &Point<usize>,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = T> + 'a {
+        let pos = *pos;
 
-            neighbors.push(GridCell {
-                position: Point::new(x, pos.y),
-                value: v,
-            })
-        }
+        directions
+            .iter()
+            .filter_map(move |&d| self.get_towards(&pos, d))
+    }
 
-        // East
-        if pos.x < self.width - 1 {
-            let x = pos.x + 1;
-            let v = self.values[pos.y][x];
+    /// Number of neighbors of a cell, including diagonals and padding cells, whose value matches
+    /// `predicate`.
+    pub fn count_neighbors(&self, pos: &Point<usize>, predicate: impl Fn(&T) -> bool) -> usize {
+        self.neighbor_values(pos, &Direction::ALL)
+            .filter(|v| predicate(v))
+            .count()
+    }
 
-            neighbors.push(GridCell {
-                position: Point::new(x, pos.y),
-                value: v,
-            })
-        }
+    /// The grid mirrored along its diagonal, rows becoming columns.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
 
-        if pos.y < self.height - 1 {
-            let y = pos.y + 1;
+    /// The grid rotated by 90° clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (y, self.height - 1 - x))
+    }
 
-            // South West
-            if pos.x > 0 {
-                let x = pos.x - 1;
-                let v = self.values[y][x];
+    /// The grid rotated by 90° counterclockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (self.width - 1 - y, x))
+    }
 
-                neighbors.push(GridCell {
-                    position: Point::new(x, y),
-                    value: v,
-                })
-            }
+    /// The grid mirrored left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        self.remap(self.width, self.height, |x, y| (self.width - 1 - x, y))
+    }
 
-            // South
-            {
-                let v = self.values[y][pos.x];
+    /// The grid mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        self.remap(self.width, self.height, |x, y| (x, self.height - 1 - y))
+    }
 
-                neighbors.push(GridCell {
-                    position: Point::new(pos.x, y),
-                    value: v,
-                })
-            }
+    /// A copy of a part of the grid, see [`Grid::view`] to borrow it instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rectangle isn't fully in the grid.
+    pub fn crop(&self, rect: Rect) -> Self {
+        self.view(rect).to_grid()
+    }
 
-            // South East
-            if pos.x < self.width - 1 {
-                let x = pos.x + 1;
-                let v = self.values[y][x];
+    /// A `width` by `height` grid, whose cell at `x`,`y` takes the value of the cell of this grid
+    /// at `source(x, y)`.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let values = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (sx, sy) = source(x, y);
+                self.values[self.offset(sx, sy)]
+            })
+            .collect();
 
-                neighbors.push(GridCell {
-                    position: Point::new(x, y),
-                    value: v,
-                })
-            }
+        Grid {
+            height,
+            width,
+            values,
+            boundary: self.boundary,
         }
-
-        neighbors
     }
 }
 
 impl<T> Grid<T>
 where
-    T: TryFrom<char>,
+    T: TryFrom<char> + Clone,
 {
-    #[tracing::instrument(skip_all)]
+    /// Parse a grid with one cell per character, see [`GridParser`] for more options.
     pub fn from_input(input: &[String]) -> Result<Self, String> {
-        let height = input.len();
-        let width = input[0].len();
-
-        let values: Result<Vec<Vec<T>>, String> = input
-            .iter()
-            .map(|r| {
-                if r.len() != width {
-                    return Err("Invalid row length".to_string());
-                }
-
-                let values: Result<Vec<T>, String> = r
-                    .chars()
-                    .map(|v| match T::try_from(v) {
-                        Ok(v) => Ok(v),
-                        Err(_) => Err(format!("Invalid value: {}", v)),
-                    })
-                    .collect();
-
-                values
-            })
-            .collect();
-
-        values.map(|v| Grid {
-            height,
-            width,
-            values: v,
-        })
+        GridParser::new(|c| T::try_from(c).ok()).parse(input)
     }
 }
 
@@ -204,13 +402,390 @@ where
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("Grid {\n")?;
 
-        for r in &self.values {
+        for y in 0..self.height {
             f.write_fmt(format_args!(
                 "{}\n",
-                r.iter().map(|&v| char::from(v)).collect::<String>()
+                self.row(y)
+                    .iter()
+                    .map(|&v| char::from(v))
+                    .collect::<String>()
             ))?;
         }
 
         f.write_str("}\n")
     }
 }
+
+impl<T> GridLike for Grid<T> {
+    type Value = T;
+    type Position = Point<usize>;
+
+    fn value(&self, pos: &Point<usize>) -> Option<&T> {
+        self.contains(pos).then(|| &self[*pos])
+    }
+
+    fn set_value(&mut self, pos: &Point<usize>, value: T) {
+        self[*pos] = value;
+    }
+
+    fn step(&self, pos: &Point<usize>, direction: Direction) -> Option<Point<usize>> {
+        Grid::step(self, pos, direction)
+    }
+
+    fn positions(&self) -> impl Iterator<Item = Point<usize>> {
+        Grid::positions(self)
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point<usize>) -> &T {
+        &self.values[self.offset(pos.x, pos.y)]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, pos: Point<usize>) -> &mut T {
+        let i = self.offset(pos.x, pos.y);
+        &mut self.values[i]
+    }
+}
+
+/// A rectangular part of a [`Grid`], with its own coordinates starting at its top left corner.
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    rect: Rect,
+}
+
+// Not derived, which would require `T: Copy`
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn width(&self) -> usize {
+        self.rect.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rect.height
+    }
+
+    /// The part of the grid this view covers.
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `y` is out of the view.
+    pub fn row(&self, y: usize) -> &'a [T] {
+        assert!(y < self.rect.height, "row {} out of the view", y);
+        &self.grid.row(self.rect.y + y)[self.rect.x..self.rect.x + self.rect.width]
+    }
+
+    /// The values of every cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + use<'a, T> {
+        let (grid, rect) = (self.grid, self.rect);
+
+        (rect.y..rect.y + rect.height).flat_map(move |y| &grid.row(y)[rect.x..rect.x + rect.width])
+    }
+
+    /// The position of every cell in the view, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point<usize>> + use<T> {
+        let width = self.rect.width;
+        (0..self.rect.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// The position in the grid of a position in the view.
+    pub fn to_grid_position(&self, pos: &Point<usize>) -> Point<usize> {
+        Point::new(self.rect.x + pos.x, self.rect.y + pos.y)
+    }
+
+    /// Copy the view into a grid of its own.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Copy,
+    {
+        Grid {
+            height: self.rect.height,
+            width: self.rect.width,
+            values: self.iter().copied().collect(),
+            boundary: self.grid.boundary,
+        }
+    }
+}
+
+impl<T> Index<Point<usize>> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Point<usize>) -> &T {
+        &self.row(pos.y)[pos.x]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rstest::{fixture, rstest};
+
+    use super::*;
+    use crate::parse_test_input;
+
+    #[fixture]
+    fn grid() -> Grid<char> {
+        Grid::from_input(&parse_test_input(
+            "
+            abc
+            def
+            ",
+        ))
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_from_input(grid: Grid<char>) {
+        assert_eq!((grid.width, grid.height), (3, 2));
+        assert_eq!(grid.iter().collect::<String>(), "abcdef");
+    }
+
+    #[rstest]
+    fn test_from_input_invalid_row_length() {
+        let res = Grid::<char>::from_input(&parse_test_input(
+            "
+            abc
+            de
+            ",
+        ));
+
+        assert_eq!(
+            res.err(),
+            Some("Row 2 has 2 cells instead of 3".to_string())
+        );
+    }
+
+    #[rstest]
+    fn test_rows_and_columns(grid: Grid<char>) {
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+    }
+
+    #[rstest]
+    #[should_panic(expected = "row 2 out of the grid")]
+    fn test_row_out_of_the_grid(grid: Grid<char>) {
+        grid.row(2);
+    }
+
+    #[rstest]
+    fn test_positions_and_cells(grid: Grid<char>) {
+        let positions = grid.positions().collect::<Vec<Point<usize>>>();
+
+        assert_eq!(positions.len(), 6);
+        assert_eq!(positions[0], Point::new(0, 0));
+        assert_eq!(positions[4], Point::new(1, 1));
+
+        let cell = grid.cells().nth(5).unwrap();
+        assert_eq!((cell.position, cell.value), (Point::new(2, 1), 'f'));
+    }
+
+    #[rstest]
+    #[case('a', Some(Point::new(0, 0)))]
+    #[case('e', Some(Point::new(1, 1)))]
+    #[case('z', None)]
+    fn test_find(grid: Grid<char>, #[case] value: char, #[case] expected: Option<Point<usize>>) {
+        assert_eq!(grid.find(&value), expected);
+    }
+
+    #[rstest]
+    fn test_index(mut grid: Grid<char>) {
+        assert_eq!(grid[Point::new(2, 0)], 'c');
+
+        grid[Point::new(2, 0)] = 'x';
+        grid.iter_mut()
+            .filter(|v| **v == 'a')
+            .for_each(|v| *v = 'y');
+
+        assert_eq!(grid.to_string(), "Grid {\nybx\ndef\n}\n");
+    }
+
+    #[rstest]
+    #[case(Point::new(2, 1), Some('f'))]
+    #[case(Point::new(3, 0), None)]
+    #[case(Point::new(0, 2), None)]
+    fn test_get(grid: Grid<char>, #[case] pos: Point<usize>, #[case] expected: Option<char>) {
+        assert_eq!(grid.get(&pos), expected);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "3,0 out of the grid")]
+    fn test_index_past_the_row(grid: Grid<char>) {
+        let _ = grid[Point::new(3, 0)];
+    }
+
+    #[rstest]
+    #[should_panic(expected = "3,0 out of the grid")]
+    fn test_set_past_the_row(mut grid: Grid<char>) {
+        grid.set(&Point::new(3, 0), 'x');
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 0), "bd", "bed")]
+    #[case(Point::new(1, 0), "cea", "cfeda")]
+    #[case(Point::new(1, 1), "bfd", "bcfda")]
+    fn test_neighbors(
+        grid: Grid<char>,
+        #[case] pos: Point<usize>,
+        #[case] expected4: &str,
+        #[case] expected8: &str,
+    ) {
+        let values = |cells: &mut dyn Iterator<Item = GridCell<char>>| {
+            cells.map(|c| c.value).collect::<String>()
+        };
+
+        assert_eq!(values(&mut grid.neighbors4(&pos)), expected4);
+        assert_eq!(values(&mut grid.neighbors8(&pos)), expected8);
+        assert_eq!(
+            values(&mut grid.get_neighbors(&pos).into_iter()),
+            expected8.chars().sorted().collect::<String>()
+        );
+    }
+
+    #[rstest]
+    fn test_neighbors_in_directions(grid: Grid<char>) {
+        let cells = grid
+            .neighbors(&Point::new(2, 0), &[Direction::North, Direction::SouthWest])
+            .collect::<Vec<GridCell<char>>>();
+
+        assert_eq!(cells.len(), 1);
+        assert_eq!((cells[0].position, cells[0].value), (Point::new(1, 1), 'e'));
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 0), 1)]
+    #[case(Point::new(1, 0), 2)]
+    #[case(Point::new(2, 1), 1)]
+    fn test_count_neighbors(grid: Grid<char>, #[case] pos: Point<usize>, #[case] expected: usize) {
+        assert_eq!(
+            grid.count_neighbors(&pos, |v| "aeiou".contains(*v)),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn test_empty_grid() {
+        let grid = Grid::<char>::from_input(&[]).unwrap();
+        let origin = Point::new(0, 0);
+
+        assert_eq!((grid.width, grid.height), (0, 0));
+        assert_eq!(grid.get_below(&origin), None);
+        assert_eq!(grid.get_right(&origin), None);
+        assert_eq!(grid.neighbors8(&origin).count(), 0);
+
+        let grid = grid.with_boundary(Boundary::Wrapping);
+        assert_eq!(grid.step(&origin, Direction::NorthWest), None);
+        assert_eq!(grid.get_above(&origin), None);
+        assert_eq!(grid.neighbors8(&origin).count(), 0);
+    }
+
+    #[rstest]
+    #[case(Boundary::Clamped, None, None)]
+    #[case(Boundary::Wrapping, Some('d'), Some(Point::new(2, 1)))]
+    #[case(Boundary::Padded('.'), Some('.'), None)]
+    fn test_boundary(
+        grid: Grid<char>,
+        #[case] boundary: Boundary<char>,
+        #[case] above: Option<char>,
+        #[case] north_west: Option<Point<usize>>,
+    ) {
+        let grid = grid.with_boundary(boundary);
+        let origin = Point::new(0, 0);
+
+        assert_eq!(grid.get_above(&origin), above);
+        assert_eq!(grid.step(&origin, Direction::NorthWest), north_west);
+        assert_eq!(grid.step(&origin, Direction::East), Some(Point::new(1, 0)));
+    }
+
+    #[rstest]
+    #[case(Boundary::Clamped, "bed", 3)]
+    #[case(Boundary::Wrapping, "debedfcf", 8)]
+    #[case(Boundary::Padded('.'), "bed", 8)]
+    fn test_neighbors_with_boundary(
+        grid: Grid<char>,
+        #[case] boundary: Boundary<char>,
+        #[case] cells: &str,
+        #[case] count: usize,
+    ) {
+        let grid = grid.with_boundary(boundary);
+        let origin = Point::new(0, 0);
+
+        assert_eq!(
+            grid.neighbors8(&origin)
+                .map(|c| c.value)
+                .collect::<String>(),
+            cells
+        );
+        assert_eq!(grid.count_neighbors(&origin, |_| true), count);
+    }
+
+    fn rows(grid: &Grid<char>) -> Vec<String> {
+        (0..grid.height)
+            .map(|y| grid.row(y).iter().collect())
+            .collect()
+    }
+
+    #[rstest]
+    fn test_transforms(grid: Grid<char>) {
+        assert_eq!(rows(&grid.transpose()), vec!["ad", "be", "cf"]);
+        assert_eq!(rows(&grid.rotate_cw()), vec!["da", "eb", "fc"]);
+        assert_eq!(rows(&grid.rotate_ccw()), vec!["cf", "be", "ad"]);
+        assert_eq!(rows(&grid.flip_horizontal()), vec!["cba", "fed"]);
+        assert_eq!(rows(&grid.flip_vertical()), vec!["def", "abc"]);
+    }
+
+    #[rstest]
+    fn test_rotations_compose(grid: Grid<char>) {
+        let full_turn = grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw();
+
+        assert!(full_turn == grid);
+        assert!(grid.rotate_cw().rotate_ccw() == grid);
+        assert!(grid.rotate_cw().rotate_cw() == grid.flip_horizontal().flip_vertical());
+    }
+
+    #[rstest]
+    fn test_view(grid: Grid<char>) {
+        let view = grid.view(Rect::new(1, 0, 2, 2));
+
+        assert_eq!((view.width(), view.height()), (2, 2));
+        assert_eq!(view.row(1), &['e', 'f']);
+        assert_eq!(view.iter().collect::<String>(), "bcef");
+        assert_eq!(view[Point::new(1, 0)], 'c');
+        assert_eq!(view.to_grid_position(&Point::new(1, 1)), Point::new(2, 1));
+        assert_eq!(view.positions().last(), Some(Point::new(1, 1)));
+    }
+
+    #[rstest]
+    fn test_crop(grid: Grid<char>) {
+        assert_eq!(rows(&grid.crop(Rect::new(0, 1, 2, 1))), vec!["de"]);
+        assert_eq!(grid.crop(Rect::new(1, 1, 0, 0)).iter().count(), 0);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "out of the grid")]
+    fn test_view_out_of_the_grid(grid: Grid<char>) {
+        grid.view(Rect::new(2, 0, 2, 1));
+    }
+
+    #[rstest]
+    fn test_new() {
+        let grid = Grid::new(2, 3, 0u8);
+
+        assert_eq!(grid.iter().count(), 6);
+        assert_eq!(grid.column(1).count(), 3);
+    }
+}
@@ -0,0 +1,362 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::iter::once;
+
+use tracing::{debug, info};
+
+use super::Grid;
+use crate::{Direction, Point};
+
+/// How the cells are updated on each step of an [`Automaton`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    /// Every cell is computed from the grid as it was before the step.
+    Synchronous,
+    /// Cells are updated row by row, later cells seeing the new values of earlier ones.
+    InPlace,
+    /// Like [`Update::InPlace`], but after the first step only the cells next to the ones that
+    /// changed are evaluated again. The rule must only depend on a cell and its 8 neighbors.
+    Frontier,
+}
+
+/// A repetition of the states of an automaton.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first step of the cycle.
+    pub start: usize,
+    /// Number of steps after which the states repeat, 1 for a fixpoint.
+    pub length: usize,
+}
+
+/// A cellular automaton, evolving a grid by applying a rule to every cell on each step.
+///
+/// The rule gives the new value of a cell from the grid and the position of the cell.
+pub struct Automaton<T, R> {
+    grid: Grid<T>,
+    buffer: Vec<T>,
+    rule: R,
+    update: Update,
+    /// Cells to evaluate on the next step in [`Update::Frontier`] mode, all of them if `None`.
+    frontier: Option<Vec<Point<usize>>>,
+    /// Which cells are already in the next frontier.
+    queued: Vec<bool>,
+    steps: usize,
+    changes: usize,
+    evaluations: usize,
+}
+
+impl<T, R> Automaton<T, R>
+where
+    T: Copy + PartialEq,
+    R: FnMut(&Grid<T>, &Point<usize>) -> T,
+{
+    /// An automaton with synchronous updates.
+    pub fn new(grid: Grid<T>, rule: R) -> Self {
+        Self {
+            grid,
+            buffer: Vec::new(),
+            rule,
+            update: Update::Synchronous,
+            frontier: None,
+            queued: Vec::new(),
+            steps: 0,
+            changes: 0,
+            evaluations: 0,
+        }
+    }
+
+    pub fn with_update(mut self, update: Update) -> Self {
+        self.update = update;
+        self
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Number of steps done so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Number of cell changes over all the steps done so far.
+    pub fn changes(&self) -> usize {
+        self.changes
+    }
+
+    /// Number of times the rule was applied over all the steps done so far.
+    pub fn evaluations(&self) -> usize {
+        self.evaluations
+    }
+
+    /// Apply the rule to every cell once, returning the number of cells that changed.
+    pub fn step(&mut self) -> usize {
+        let evaluations = self.evaluations;
+
+        let changed = match self.update {
+            Update::Synchronous => {
+                self.buffer.clear();
+                for p in self.grid.positions() {
+                    self.buffer.push((self.rule)(&self.grid, &p));
+                }
+                self.evaluations += self.buffer.len();
+
+                let changed = self
+                    .grid
+                    .values
+                    .iter()
+                    .zip(&self.buffer)
+                    .filter(|(a, b)| a != b)
+                    .count();
+                std::mem::swap(&mut self.grid.values, &mut self.buffer);
+
+                changed
+            }
+            Update::InPlace => {
+                let mut changed = 0;
+                for p in self.grid.positions() {
+                    self.evaluations += 1;
+                    let v = (self.rule)(&self.grid, &p);
+                    if v != self.grid[p] {
+                        self.grid[p] = v;
+                        changed += 1;
+                    }
+                }
+
+                changed
+            }
+            Update::Frontier => self.step_frontier(),
+        };
+
+        debug!(
+            step = self.steps,
+            evaluated = self.evaluations - evaluations,
+            changed,
+            "Step done"
+        );
+
+        self.steps += 1;
+        self.changes += changed;
+
+        changed
+    }
+
+    fn step_frontier(&mut self) -> usize {
+        let frontier = self
+            .frontier
+            .take()
+            .unwrap_or_else(|| self.grid.positions().collect());
+
+        // The flags only track the next frontier, so they are reset before the pass rather than as
+        // cells are evaluated, which would let them be queued twice
+        self.queued.resize(self.grid.values.len(), false);
+        for p in &frontier {
+            self.queued[self.grid.offset(p.x, p.y)] = false;
+        }
+        let mut next = Vec::new();
+        let mut changed = 0;
+
+        for p in frontier {
+            self.evaluations += 1;
+
+            let v = (self.rule)(&self.grid, &p);
+            if v == self.grid[p] {
+                continue;
+            }
+
+            self.grid[p] = v;
+            changed += 1;
+
+            let neighbors = Direction::ALL.iter().filter_map(|&d| self.grid.step(&p, d));
+            for n in once(p).chain(neighbors) {
+                let i = self.grid.offset(n.x, n.y);
+                if !self.queued[i] {
+                    self.queued[i] = true;
+                    next.push(n);
+                }
+            }
+        }
+
+        self.frontier = Some(next);
+
+        changed
+    }
+
+    /// Do `n` steps, returning the number of cells that changed.
+    pub fn run(&mut self, n: usize) -> usize {
+        (0..n).map(|_| self.step()).sum()
+    }
+
+    /// Step until the grid doesn't change anymore, returning the number of steps that changed it.
+    ///
+    /// This never returns if the grid oscillates, which only [`Update::Synchronous`] allows: use
+    /// [`Automaton::find_cycle`] for rules that may do it.
+    pub fn run_to_fixpoint(&mut self) -> usize {
+        let start = self.steps;
+
+        while self.step() > 0 {}
+
+        let passes = self.steps - start - 1;
+        info!(
+            passes,
+            evaluated = self.evaluations,
+            changes = self.changes,
+            "Fixpoint reached"
+        );
+
+        passes
+    }
+
+    /// Step until the grid gets back to a state it was already in. States are looked up by hash,
+    /// then compared in full so that a hash collision isn't taken for a cycle.
+    pub fn find_cycle(&mut self) -> Cycle
+    where
+        T: Hash,
+    {
+        let mut seen: HashMap<u64, Vec<(Vec<T>, usize)>> = HashMap::new();
+
+        loop {
+            let mut hasher = DefaultHasher::new();
+            self.grid.values.hash(&mut hasher);
+
+            let states = seen.entry(hasher.finish()).or_default();
+            if let Some(&(_, start)) = states.iter().find(|(v, _)| *v == self.grid.values) {
+                return Cycle {
+                    start,
+                    length: self.steps - start,
+                };
+            }
+            states.push((self.grid.values.clone(), self.steps));
+
+            self.step();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::parse_test_input;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::from_input(&parse_test_input(input)).unwrap()
+    }
+
+    /// Conway's game of life.
+    fn life(grid: &Grid<char>, p: &Point<usize>) -> char {
+        match (grid[*p], grid.count_neighbors(p, |&c| c == '#')) {
+            ('#', 2 | 3) | ('.', 3) => '#',
+            _ => '.',
+        }
+    }
+
+    #[rstest]
+    fn test_blinker_cycle() {
+        let blinker = grid(
+            "
+            .....
+            ..#..
+            ..#..
+            ..#..
+            .....
+            ",
+        );
+        let mut automaton = Automaton::new(blinker, life);
+
+        assert_eq!(automaton.step(), 4);
+        assert_eq!(
+            automaton.grid().to_string(),
+            "Grid {\n.....\n.....\n.###.\n.....\n.....\n}\n"
+        );
+
+        let cycle = automaton.find_cycle();
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 1,
+                length: 2
+            }
+        );
+        assert_eq!(automaton.steps(), 3);
+    }
+
+    #[rstest]
+    fn test_fixpoint() {
+        let block = grid(
+            "
+            ....
+            .##.
+            .#..
+            ....
+            ",
+        );
+        let mut automaton = Automaton::new(block, life);
+
+        assert_eq!(automaton.run_to_fixpoint(), 1);
+        assert_eq!(automaton.changes(), 1);
+        assert_eq!(automaton.find_cycle().length, 1);
+    }
+
+    #[rstest]
+    #[case(Update::Synchronous, 1, 1, "##..")]
+    #[case(Update::Synchronous, 3, 3, "####")]
+    #[case(Update::InPlace, 1, 3, "####")]
+    #[case(Update::Frontier, 1, 3, "####")]
+    fn test_update(
+        #[case] update: Update,
+        #[case] steps: usize,
+        #[case] changes: usize,
+        #[case] expected: &str,
+    ) {
+        // Cells copy their left neighbor
+        let spread = |grid: &Grid<char>, p: &Point<usize>| grid.get_left(p).unwrap_or(grid[*p]);
+        let mut automaton = Automaton::new(grid("#..."), spread).with_update(update);
+
+        assert_eq!(automaton.run(steps), changes);
+        assert_eq!(
+            automaton.into_grid().row(0).iter().collect::<String>(),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn test_frontier_queues_cells_once() {
+        let spread = |grid: &Grid<char>, p: &Point<usize>| grid.get_left(p).unwrap_or(grid[*p]);
+        let mut automaton =
+            Automaton::new(grid("#...\n.#..\n..#."), spread).with_update(Update::Frontier);
+
+        while automaton.step() > 0 {
+            let frontier = automaton.frontier.as_ref().unwrap();
+            let unique = frontier.iter().collect::<std::collections::HashSet<_>>();
+
+            assert_eq!(unique.len(), frontier.len());
+        }
+    }
+
+    #[rstest]
+    fn test_frontier_evaluates_less() {
+        // Cells are emptied when their right neighbor is, so the row empties one cell per pass
+        let shrink = |grid: &Grid<char>, p: &Point<usize>| match grid.get_right(p) {
+            Some('.') => '.',
+            _ => grid[*p],
+        };
+        let row = grid(&format!("{}.", "#".repeat(20)));
+
+        let mut in_place = Automaton::new(row.clone(), shrink).with_update(Update::InPlace);
+        let mut frontier = Automaton::new(row, shrink).with_update(Update::Frontier);
+
+        assert_eq!(in_place.run_to_fixpoint(), 20);
+        assert_eq!(frontier.run_to_fixpoint(), 20);
+        assert_eq!(frontier.changes(), in_place.changes());
+        assert_eq!(frontier.grid().to_string(), in_place.grid().to_string());
+        assert_eq!(in_place.evaluations(), 21 * 21);
+        assert_eq!(frontier.evaluations(), 21 + 19 * 3 + 2);
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use super::{Boundary, Grid};
+use crate::Point;
+
+/// Builds a [`Grid`] from the lines of an input, mapping every character to a value.
+///
+/// Rows and columns are numbered from 1 in the errors, like in an editor.
+pub struct GridParser<T, F> {
+    map: F,
+    padding: Option<T>,
+    markers: HashMap<char, T>,
+}
+
+impl<T, F> GridParser<T, F>
+where
+    T: Clone,
+    F: Fn(char) -> Option<T>,
+{
+    /// A parser rejecting ragged rows, and characters for which `map` returns `None`.
+    pub fn new(map: F) -> Self {
+        Self {
+            map,
+            padding: None,
+            markers: HashMap::new(),
+        }
+    }
+
+    /// Pad the rows shorter than the longest one on the right with `value`, instead of rejecting
+    /// them.
+    pub fn with_padding(mut self, value: T) -> Self {
+        self.padding = Some(value);
+        self
+    }
+
+    /// Record the positions of the `marker` characters, and put `floor` in their cells.
+    pub fn with_marker(mut self, marker: char, floor: T) -> Self {
+        self.markers.insert(marker, floor);
+        self
+    }
+
+    pub fn parse(&self, input: &[String]) -> Result<Grid<T>, String> {
+        self.parse_with_markers(input).map(|(grid, _)| grid)
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub fn parse_with_markers(&self, input: &[String]) -> Result<(Grid<T>, Markers), String> {
+        let height = input.len();
+        let width = match self.padding {
+            Some(_) => input.iter().map(|r| r.chars().count()).max(),
+            None => input.first().map(|r| r.chars().count()),
+        }
+        .unwrap_or(0);
+
+        let mut values = Vec::with_capacity(width * height);
+        let mut markers = Markers::default();
+
+        for (y, r) in input.iter().enumerate() {
+            let start = values.len();
+
+            for (x, c) in r.chars().enumerate() {
+                let value = match self.markers.get(&c) {
+                    Some(floor) => {
+                        markers.0.entry(c).or_default().push(Point::new(x, y));
+                        floor.clone()
+                    }
+                    None => (self.map)(c).ok_or_else(|| {
+                        format!(
+                            "Invalid character {:?} at row {}, column {}",
+                            c,
+                            y + 1,
+                            x + 1
+                        )
+                    })?,
+                };
+                values.push(value);
+            }
+
+            let len = values.len() - start;
+            match &self.padding {
+                _ if len == width => {}
+                Some(padding) => values.resize(start + width, padding.clone()),
+                None => {
+                    return Err(format!(
+                        "Row {} has {} cells instead of {}",
+                        y + 1,
+                        len,
+                        width
+                    ));
+                }
+            }
+        }
+
+        let grid = Grid {
+            height,
+            width,
+            values,
+            boundary: Boundary::Clamped,
+        };
+
+        Ok((grid, markers))
+    }
+}
+
+/// The positions of the markers found by a [`GridParser`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Markers(HashMap<char, Vec<Point<usize>>>);
+
+impl Markers {
+    /// The positions of a marker, in row order.
+    pub fn get(&self, marker: char) -> &[Point<usize>] {
+        self.0.get(&marker).map_or(&[], |p| p.as_slice())
+    }
+
+    /// The position of a marker that must appear exactly once.
+    pub fn single(&self, marker: char) -> Result<Point<usize>, String> {
+        match self.get(marker) {
+            [p] => Ok(*p),
+            [] => Err(format!("Marker {:?} not found", marker)),
+            p => Err(format!("Marker {:?} found {} times", marker, p.len())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::parse_test_input;
+
+    fn wall(c: char) -> Option<bool> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    #[rstest]
+    fn test_markers() {
+        let input = parse_test_input(
+            "
+            #S..
+            .#E.
+            ..#E
+            ",
+        );
+
+        let (grid, markers) = GridParser::new(wall)
+            .with_marker('S', false)
+            .with_marker('E', false)
+            .parse_with_markers(&input)
+            .unwrap();
+
+        assert_eq!((grid.width, grid.height), (4, 3));
+        assert_eq!(grid.iter().filter(|&&w| w).count(), 3);
+        assert_eq!(markers.single('S'), Ok(Point::new(1, 0)));
+        assert_eq!(markers.get('E'), &[Point::new(2, 1), Point::new(3, 2)]);
+        assert_eq!(
+            markers.single('E'),
+            Err("Marker 'E' found 2 times".to_string())
+        );
+        assert_eq!(markers.single('X'), Err("Marker 'X' not found".to_string()));
+    }
+
+    #[rstest]
+    fn test_padding() {
+        let input = vec!["ab".to_string(), "abcd".to_string(), String::new()];
+
+        let grid = GridParser::new(Some)
+            .with_padding(' ')
+            .parse(&input)
+            .unwrap();
+
+        assert_eq!((grid.width, grid.height), (4, 3));
+        assert_eq!(grid.to_string(), "Grid {\nab  \nabcd\n    \n}\n");
+    }
+
+    #[rstest]
+    #[case(&["#.", "#"], "Row 2 has 1 cells instead of 2")]
+    #[case(&["#.", "#.#"], "Row 2 has 3 cells instead of 2")]
+    #[case(&["##", "#.", ".x"], "Invalid character 'x' at row 3, column 2")]
+    fn test_errors(#[case] input: &[&str], #[case] expected: &str) {
+        let input = input.iter().map(|r| r.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            GridParser::new(wall).parse(&input).err(),
+            Some(expected.to_string())
+        );
+    }
+
+    #[rstest]
+    fn test_empty_input() {
+        let grid = GridParser::new(wall)
+            .with_padding(false)
+            .parse(&[])
+            .unwrap();
+
+        assert_eq!((grid.width, grid.height), (0, 0));
+    }
+}
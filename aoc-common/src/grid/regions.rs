@@ -0,0 +1,353 @@
+use std::collections::VecDeque;
+
+use super::Grid;
+use crate::{Direction, Point};
+
+/// Which cells are connected to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Cells sharing a side.
+    Four,
+    /// Cells sharing a side or a corner.
+    Eight,
+}
+
+impl Connectivity {
+    pub fn directions(&self) -> &'static [Direction] {
+        match self {
+            Connectivity::Four => &Direction::CARDINAL,
+            Connectivity::Eight => &Direction::ALL,
+        }
+    }
+}
+
+/// Identifies a region of a grid. Displayed as a letter or a digit, which are reused when there are
+/// more than 62 regions.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Label(pub usize);
+
+impl From<Label> for char {
+    fn from(label: Label) -> Self {
+        const SYMBOLS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+        SYMBOLS[label.0 % SYMBOLS.len()] as char
+    }
+}
+
+/// Connected cells with the same value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region<T> {
+    label: Label,
+    value: T,
+    cells: Vec<Point<usize>>,
+    perimeter: usize,
+    sides: usize,
+}
+
+impl<T> Region<T> {
+    pub fn label(&self) -> Label {
+        self.label
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// The positions of the cells of the region, in the order they were reached.
+    pub fn cells(&self) -> &[Point<usize>] {
+        &self.cells
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Number of cell sides on the border of the region.
+    pub fn perimeter(&self) -> usize {
+        self.perimeter
+    }
+
+    /// Number of straight sides of the border of the region, which is also its number of corners.
+    pub fn sides(&self) -> usize {
+        self.sides
+    }
+}
+
+/// The regions of a grid, and the grid of the label of the region every cell is in.
+#[derive(Clone)]
+pub struct Regions<T> {
+    pub labels: Grid<Label>,
+    pub regions: Vec<Region<T>>,
+}
+
+impl<T> Regions<T> {
+    /// The region a cell is in.
+    pub fn region_at(&self, pos: &Point<usize>) -> &Region<T> {
+        &self.regions[self.labels[*pos].0]
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Copy + PartialEq,
+{
+    /// The positions of the cells connected to `start` through cells with the same value, `start`
+    /// included.
+    pub fn region(&self, start: &Point<usize>, connectivity: Connectivity) -> Vec<Point<usize>> {
+        let mut seen = Grid::new(self.width, self.height, false);
+        self.explore(start, connectivity, &mut seen)
+    }
+
+    /// Set the value of the region of `start` to `value`, returning the number of cells changed.
+    pub fn flood_fill(
+        &mut self,
+        start: &Point<usize>,
+        value: T,
+        connectivity: Connectivity,
+    ) -> usize {
+        if self[*start] == value {
+            return 0;
+        }
+
+        let cells = self.region(start, connectivity);
+        for &p in &cells {
+            self[p] = value;
+        }
+
+        cells.len()
+    }
+
+    /// Split the grid into regions of connected cells with the same value. Regions follow the
+    /// boundary of the grid, so they go across the edges of a wrapping grid.
+    pub fn components(&self, connectivity: Connectivity) -> Regions<T> {
+        let mut seen = Grid::new(self.width, self.height, false);
+        let mut labels = Grid::new(self.width, self.height, Label(0));
+        let mut cells = Vec::new();
+
+        for p in self.positions() {
+            if seen[p] {
+                continue;
+            }
+
+            let label = Label(cells.len());
+            let region = self.explore(&p, connectivity, &mut seen);
+            for &c in &region {
+                labels[c] = label;
+            }
+
+            cells.push(region);
+        }
+
+        let regions = cells
+            .into_iter()
+            .enumerate()
+            .map(|(i, cells)| {
+                let (perimeter, sides) = border(&labels, &cells, |p, d| self.step(p, d));
+
+                Region {
+                    label: Label(i),
+                    value: self[cells[0]],
+                    cells,
+                    perimeter,
+                    sides,
+                }
+            })
+            .collect();
+
+        Regions { labels, regions }
+    }
+
+    /// Breadth-first walk of the region of `start`, marking its cells as seen.
+    fn explore(
+        &self,
+        start: &Point<usize>,
+        connectivity: Connectivity,
+        seen: &mut Grid<bool>,
+    ) -> Vec<Point<usize>> {
+        let value = self[*start];
+        let mut cells = vec![*start];
+        let mut queue = VecDeque::from([*start]);
+        seen[*start] = true;
+
+        while let Some(p) = queue.pop_front() {
+            for &d in connectivity.directions() {
+                if let Some(n) = self.step(&p, d)
+                    && !seen[n]
+                    && self[n] == value
+                {
+                    seen[n] = true;
+                    cells.push(n);
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        cells
+    }
+}
+
+/// The perimeter and number of sides of a region, from the labels of the cells around it. Cells
+/// are moved between with `step`, which follows the boundary of the grid the regions are from.
+fn border(
+    labels: &Grid<Label>,
+    cells: &[Point<usize>],
+    step: impl Fn(&Point<usize>, Direction) -> Option<Point<usize>>,
+) -> (usize, usize) {
+    let label = labels[cells[0]];
+    let same = |p: &Point<usize>, d: Direction| step(p, d).is_some_and(|n| labels[n] == label);
+
+    let mut perimeter = 0;
+    let mut corners = 0;
+
+    for p in cells {
+        perimeter += Direction::CARDINAL.iter().filter(|&&d| !same(p, d)).count();
+
+        // A corner of the cell is a corner of the region if both its sides are on the border, or
+        // if neither is but the cell in the diagonal is out of the region
+        for d in Direction::CARDINAL {
+            let next = d.turn_right();
+            let diagonal = step(p, d)
+                .and_then(|n| step(&n, next))
+                .is_some_and(|n| labels[n] == label);
+
+            match (same(p, d), same(p, next)) {
+                (false, false) => corners += 1,
+                (true, true) if !diagonal => corners += 1,
+                _ => {}
+            }
+        }
+    }
+
+    (perimeter, corners)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::{fixture, rstest};
+
+    use super::*;
+    use crate::grid::Boundary;
+    use crate::parse_test_input;
+
+    #[fixture]
+    fn garden() -> Grid<char> {
+        Grid::from_input(&parse_test_input(
+            "
+            AAAA
+            BBCD
+            BBCC
+            EEEC
+            ",
+        ))
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_components(garden: Grid<char>) {
+        let regions = garden.components(Connectivity::Four);
+
+        let metrics = regions
+            .regions
+            .iter()
+            .map(|r| (*r.value(), r.area(), r.perimeter(), r.sides()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            metrics,
+            vec![
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4),
+            ]
+        );
+        assert_eq!(regions.region_at(&Point::new(3, 3)).label(), Label(2));
+    }
+
+    #[rstest]
+    fn test_labels_display(garden: Grid<char>) {
+        let regions = garden
+            .map(|&c| if c == 'C' { 'x' } else { '.' })
+            .components(Connectivity::Eight);
+
+        assert_eq!(regions.regions.len(), 2);
+        assert_eq!(
+            regions.labels.to_string(),
+            "Grid {\nAAAA\nAABA\nAABB\nAAAB\n}\n"
+        );
+    }
+
+    #[rstest]
+    fn test_region_with_holes() {
+        let grid = Grid::<char>::from_input(&parse_test_input(
+            "
+            OOOOO
+            OXOXO
+            OOOOO
+            OXOXO
+            OOOOO
+            ",
+        ))
+        .unwrap();
+
+        let regions = grid.components(Connectivity::Four);
+        let outer = regions.region_at(&Point::new(0, 0));
+
+        assert_eq!(regions.regions.len(), 5);
+        assert_eq!(
+            (outer.area(), outer.perimeter(), outer.sides()),
+            (21, 36, 20)
+        );
+    }
+
+    #[rstest]
+    fn test_components_wrapping() {
+        let grid = Grid::<char>::from_input(&parse_test_input(
+            "
+            A..A
+            A..A
+            ....
+            ",
+        ))
+        .unwrap()
+        .with_boundary(Boundary::Wrapping);
+
+        let regions = grid.components(Connectivity::Four);
+        let a = regions.region_at(&Point::new(0, 0));
+
+        // The two columns of A are one square across the left and right edges
+        assert_eq!(regions.regions.len(), 2);
+        assert_eq!((a.area(), a.perimeter(), a.sides()), (4, 8, 4));
+    }
+
+    #[rstest]
+    #[case(Connectivity::Four, 3)]
+    #[case(Connectivity::Eight, 5)]
+    fn test_region(#[case] connectivity: Connectivity, #[case] expected: usize) {
+        let grid = Grid::<char>::from_input(&parse_test_input(
+            "
+            ##..
+            .#..
+            ..##
+            ",
+        ))
+        .unwrap();
+
+        assert_eq!(grid.region(&Point::new(0, 0), connectivity).len(), expected);
+    }
+
+    #[rstest]
+    fn test_flood_fill(mut garden: Grid<char>) {
+        assert_eq!(
+            garden.flood_fill(&Point::new(0, 1), 'z', Connectivity::Four),
+            4
+        );
+        assert_eq!(
+            garden.flood_fill(&Point::new(0, 1), 'z', Connectivity::Four),
+            0
+        );
+
+        assert_eq!(garden.to_string(), "Grid {\nAAAA\nzzCD\nzzCC\nEEEC\n}\n");
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use super::GridLike;
+use crate::{Direction, Point};
+
+/// An unbounded grid storing only the cells that are set, for maps too large or too sparse to be
+/// stored densely. Coordinates can be negative.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point<i64>, T>,
+    bounds: Option<(Point<i64>, Point<i64>)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, pos: &Point<i64>) -> Option<&T> {
+        self.cells.get(pos)
+    }
+
+    pub fn get_mut(&mut self, pos: &Point<i64>) -> Option<&mut T> {
+        self.cells.get_mut(pos)
+    }
+
+    pub fn contains(&self, pos: &Point<i64>) -> bool {
+        self.cells.contains_key(pos)
+    }
+
+    /// Set a cell, growing the bounds to include it. Returns the previous value of the cell.
+    pub fn insert(&mut self, pos: Point<i64>, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                Point::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Point::new(max.x.max(pos.x), max.y.max(pos.y)),
+            ),
+            None => (pos, pos),
+        });
+
+        self.cells.insert(pos, value)
+    }
+
+    /// Unset a cell. The bounds are not shrunk.
+    pub fn remove(&mut self, pos: &Point<i64>) -> Option<T> {
+        self.cells.remove(pos)
+    }
+
+    /// The top left and bottom right corners of the smallest rectangle containing every cell that
+    /// has been set, `None` if no cell was ever set.
+    pub fn bounds(&self) -> Option<(Point<i64>, Point<i64>)> {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.x - min.x + 1) as usize)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.y - min.y + 1) as usize)
+    }
+
+    /// Every cell that is set with its position, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Point<i64>, &T)> {
+        self.cells.iter()
+    }
+}
+
+impl<T> FromIterator<(Point<i64>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point<i64>, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for (pos, value) in iter {
+            grid.insert(pos, value);
+        }
+
+        grid
+    }
+}
+
+impl<T> GridLike for SparseGrid<T> {
+    type Value = T;
+    type Position = Point<i64>;
+
+    fn value(&self, pos: &Point<i64>) -> Option<&T> {
+        self.get(pos)
+    }
+
+    fn set_value(&mut self, pos: &Point<i64>, value: T) {
+        self.insert(*pos, value);
+    }
+
+    /// The grid is unbounded, there is always a position next to another.
+    fn step(&self, pos: &Point<i64>, direction: Direction) -> Option<Point<i64>> {
+        Some(pos.step(direction))
+    }
+
+    fn positions(&self) -> impl Iterator<Item = Point<i64>> {
+        self.cells.keys().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashSet, VecDeque};
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::grid::Grid;
+    use crate::parse_test_input;
+
+    #[rstest]
+    fn test_insert_and_bounds() {
+        let mut grid = SparseGrid::new();
+
+        assert_eq!(grid.bounds(), None);
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+
+        grid.insert(Point::new(3, -2), 'a');
+        grid.insert(Point::new(-5, 7), 'b');
+        assert_eq!(grid.insert(Point::new(3, -2), 'c'), Some('a'));
+
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.get(&Point::new(3, -2)), Some(&'c'));
+        assert_eq!(grid.bounds(), Some((Point::new(-5, -2), Point::new(3, 7))));
+        assert_eq!((grid.width(), grid.height()), (9, 10));
+
+        assert_eq!(grid.remove(&Point::new(-5, 7)), Some('b'));
+        assert!(!grid.contains(&Point::new(-5, 7)));
+        assert_eq!(grid.width(), 9);
+    }
+
+    #[rstest]
+    fn test_large_coordinates() {
+        let grid = [
+            (Point::new(97_000, 50_000), '#'),
+            (Point::new(-1_000_000, 3), '#'),
+        ]
+        .into_iter()
+        .collect::<SparseGrid<char>>();
+
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.width(), 1_097_001);
+    }
+
+    /// Number of cells with the same value as `start` reachable from it.
+    fn region_size<G>(grid: &G, start: G::Position) -> usize
+    where
+        G: GridLike,
+        G::Value: PartialEq,
+    {
+        let value = grid.value(&start);
+        let mut seen = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(p) = queue.pop_front() {
+            for n in grid.adjacent(&p, &Direction::CARDINAL) {
+                if grid.value(&n) == value && seen.insert(n) {
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        seen.len()
+    }
+
+    #[rstest]
+    fn test_same_algorithm_on_both_grids() {
+        let input = parse_test_input(
+            "
+            ##.
+            .#.
+            .##
+            ",
+        );
+        let grid = Grid::<char>::from_input(&input).unwrap();
+        let sparse = grid
+            .cells()
+            .filter(|c| c.value == '#')
+            .map(|c| (Point::<i64>::from(c.position), c.value))
+            .collect::<SparseGrid<char>>();
+
+        assert_eq!(region_size(&grid, Point::new(0, 0)), 5);
+        assert_eq!(region_size(&sparse, Point::new(0, 0)), 5);
+        assert_eq!(region_size(&grid, Point::new(2, 0)), 2);
+    }
+}
@@ -0,0 +1,169 @@
+use std::env;
+use std::ffi::OsString;
+use std::fmt::Display;
+use std::fs::{File, read_to_string};
+use std::io::{BufRead, BufReader, Read, stdin};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use super::Error;
+
+/// Environment variable overriding the directory the inputs, answers and benchmark history are
+/// kept in.
+pub const ROOT_DIR_VAR: &str = "AOC_ROOT_DIR";
+
+/// Environment variable overriding the directory puzzle inputs are read from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// The directory the inputs, answers and benchmark history are kept in: `$AOC_ROOT_DIR` if set,
+/// otherwise the current directory. Cargo sets it to the root of the workspace.
+pub fn root_dir() -> PathBuf {
+    dir_from(env::var_os(ROOT_DIR_VAR)).unwrap_or_default()
+}
+
+/// The directory puzzle inputs are read from: `$AOC_INPUT_DIR` if set, otherwise `input` in the
+/// [`root_dir`].
+pub fn input_dir() -> PathBuf {
+    dir_from(env::var_os(INPUT_DIR_VAR)).unwrap_or_else(|| root_dir().join("input"))
+}
+
+/// The directory set in an environment variable, if it isn't empty.
+fn dir_from(var: Option<OsString>) -> Option<PathBuf> {
+    var.filter(|d| !d.is_empty()).map(PathBuf::from)
+}
+
+/// Where a puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// A file in the input directory.
+    pub fn named(filename: &str) -> Self {
+        InputSource::File(input_dir().join(filename))
+    }
+
+    /// The default input file of a day, `dayXX.txt` in the input directory.
+    pub fn for_day(day: u8) -> Self {
+        Self::named(&format!("day{:02}.txt", day))
+    }
+
+    pub fn read_lines(&self) -> Result<Vec<String>, Error> {
+        match self {
+            InputSource::File(path) => {
+                let file = File::open(path).map_err(|e| Error::io(path.clone(), e))?;
+
+                BufReader::new(file)
+                    .lines()
+                    .collect::<Result<_, _>>()
+                    .map_err(|e| Error::io(path.clone(), e))
+            }
+            InputSource::Stdin => stdin()
+                .lock()
+                .lines()
+                .collect::<Result<_, _>>()
+                .map_err(|e| Error::io(PathBuf::from("<stdin>"), e)),
+        }
+    }
+
+    /// Read the whole input, with surrounding whitespace removed.
+    pub fn read_to_string(&self) -> Result<String, Error> {
+        let input = match self {
+            InputSource::File(path) => read_to_string(path).map_err(|e| Error::io(path.clone(), e)),
+            InputSource::Stdin => {
+                let mut buf = String::new();
+                stdin()
+                    .lock()
+                    .read_to_string(&mut buf)
+                    .map_err(|e| Error::io(PathBuf::from("<stdin>"), e))
+                    .map(|_| buf)
+            }
+        }?;
+
+        Ok(input.trim().to_owned())
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err("Empty input path".to_string()),
+            "-" => Ok(InputSource::Stdin),
+            p => Ok(InputSource::File(PathBuf::from(p))),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => f.write_str("<stdin>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::testing::TempPath;
+
+    #[rstest]
+    fn test_root_dir_is_workspace_under_cargo() {
+        assert!(root_dir().join("Cargo.toml").exists());
+        assert!(root_dir().join("aoc-common").is_dir());
+    }
+
+    #[rstest]
+    fn test_input_dir_defaults_to_root_input() {
+        if env::var_os(INPUT_DIR_VAR).is_none() {
+            assert_eq!(input_dir(), root_dir().join("input"));
+        }
+    }
+
+    #[rstest]
+    #[case(Some(OsString::from("/tmp/inputs")), Some("/tmp/inputs"))]
+    #[case(Some(OsString::from("inputs")), Some("inputs"))]
+    #[case(Some(OsString::new()), None)]
+    #[case(None, None)]
+    fn test_dir_from(#[case] var: Option<OsString>, #[case] expected: Option<&str>) {
+        assert_eq!(dir_from(var), expected.map(PathBuf::from));
+    }
+
+    #[rstest]
+    #[case("-", InputSource::Stdin)]
+    #[case("day01.txt", InputSource::File(PathBuf::from("day01.txt")))]
+    #[case("/tmp/a/b.txt", InputSource::File(PathBuf::from("/tmp/a/b.txt")))]
+    fn test_parse_input_source(#[case] input: &str, #[case] expected: InputSource) {
+        assert_eq!(input.parse::<InputSource>().unwrap(), expected);
+    }
+
+    #[rstest]
+    fn test_read_file() {
+        let input = TempPath::with_content("input.txt", "abc\n123\n\n");
+
+        let source = InputSource::File(input.path().to_path_buf());
+        let lines = source.read_lines();
+        let string = source.read_to_string();
+
+        assert_eq!(lines.unwrap(), vec!["abc", "123", ""]);
+        assert_eq!(string.unwrap(), "abc\n123");
+    }
+
+    #[rstest]
+    fn test_read_missing_file() {
+        let source = InputSource::File(PathBuf::from("/does/not/exist.txt"));
+
+        assert!(matches!(source.read_lines(), Err(Error::MissingInput(_))));
+        assert!(matches!(
+            source.read_to_string(),
+            Err(Error::MissingInput(_))
+        ));
+    }
+}
@@ -1,6 +1,4 @@
 use std::fmt::{Debug, Display};
-use std::fs::{File, read_to_string};
-use std::io::{BufRead, BufReader};
 use std::ops::{Add, Mul, Sub};
 use std::str::FromStr;
 
@@ -10,45 +8,121 @@ use tracing_subscriber::EnvFilter;
 use tracing_subscriber::filter::LevelFilter;
 use tracing_subscriber::fmt::format::FmtSpan;
 
-pub mod grid;
+// The derive macros refer to this crate by name, including in its own types
+extern crate self as aoc_common;
 
+pub mod answers;
+pub mod bench;
+mod error;
+pub mod fetch;
+pub mod grid;
+mod input;
+pub mod output;
+pub mod parse;
+mod point;
+pub mod render;
+pub mod search;
+mod solution;
+#[cfg(test)]
+mod testing;
+pub mod viewer;
+
+pub use error::Error;
+pub use input::{INPUT_DIR_VAR, InputSource, ROOT_DIR_VAR, input_dir, root_dir};
+pub use point::{Coordinate, Direction, Point};
+pub use solution::{PuzzleInput, Report, Runnable, Solution, Timings};
+
+/// Read the puzzle input file as a list of lines.
+///
+/// # Panics
+///
+/// Panics if the file can't be read, see [`try_get_input`] for a fallible version.
 pub fn get_input(filename: &str) -> Vec<String> {
-    let path = format!("{}/../input/{}", env!("CARGO_MANIFEST_DIR"), filename);
-    let file = match File::open(path) {
-        Ok(file) => file,
-        Err(error) => panic!("Unable to open file {}: {}", filename, error),
-    };
-
-    let reader = BufReader::new(file);
-
-    reader.lines().map(|l| l.unwrap()).collect()
+    try_get_input(filename).unwrap_or_else(|e| panic!("{}", e))
 }
 
+/// Read the puzzle input file as a single string, with surrounding whitespace removed.
+///
+/// # Panics
+///
+/// Panics if the file can't be read, see [`try_get_input_as_string`] for a fallible version.
 pub fn get_input_as_string(filename: &str) -> String {
-    let path = format!("{}/../input/{}", env!("CARGO_MANIFEST_DIR"), filename);
-    let reader = match read_to_string(path) {
-        Ok(r) => r,
-        Err(error) => panic!("Unable to open file {}: {}", filename, error),
-    };
-
-    reader.trim().parse().unwrap()
+    try_get_input_as_string(filename).unwrap_or_else(|e| panic!("{}", e))
 }
 
+/// Read the puzzle input file as a list of numbers, one per line.
+///
+/// # Panics
+///
+/// Panics if the file can't be read or if a line isn't a valid number, see
+/// [`try_get_input_as_int`] for a fallible version.
 pub fn get_input_as_int<T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Ord + FromStr>(
     filename: &str,
 ) -> Vec<T>
 where
     <T as FromStr>::Err: Debug,
 {
-    get_input(filename)
+    parse_lines_with(&get_input(filename), |e| format!("{:?}", e))
+        .unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Read a file from the input directory as a list of lines, see [`input_dir`].
+pub fn try_get_input(filename: &str) -> Result<Vec<String>, Error> {
+    InputSource::named(filename).read_lines()
+}
+
+/// Read a file from the input directory as a single string, with surrounding whitespace removed.
+pub fn try_get_input_as_string(filename: &str) -> Result<String, Error> {
+    InputSource::named(filename).read_to_string()
+}
+
+/// Read a file from the input directory as a list of numbers, one per line, reporting the first
+/// invalid line.
+pub fn try_get_input_as_int<
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Ord + FromStr,
+>(
+    filename: &str,
+) -> Result<Vec<T>, Error>
+where
+    <T as FromStr>::Err: Display,
+{
+    parse_lines(&try_get_input(filename)?)
+}
+
+/// Parse every line of the input, reporting the line number and text of the first invalid one.
+pub fn parse_lines<T: FromStr>(input: &[String]) -> Result<Vec<T>, Error>
+where
+    <T as FromStr>::Err: Display,
+{
+    parse_lines_with(input, |e: T::Err| e.to_string())
+}
+
+/// Parse every line of the input, describing the error of an invalid line with `message`.
+fn parse_lines_with<T: FromStr>(
+    input: &[String],
+    message: impl Fn(T::Err) -> String,
+) -> Result<Vec<T>, Error> {
+    input
         .iter()
-        .map(|i| i.parse().unwrap())
+        .enumerate()
+        .map(|(i, l)| {
+            l.parse().map_err(|e| Error::Parse {
+                line: i + 1,
+                text: l.clone(),
+                message: message(e),
+            })
+        })
         .collect()
 }
 
 pub fn tracing_init() {
+    tracing_init_with_level(LevelFilter::INFO);
+}
+
+/// Initialize tracing with another default level than `INFO`, `RUST_LOG` still takes precedence.
+pub fn tracing_init_with_level(level: LevelFilter) {
     let env_filter = EnvFilter::builder()
-        .with_default_directive(LevelFilter::INFO.into())
+        .with_default_directive(level.into())
         .from_env_lossy();
 
     tracing_subscriber::fmt()
@@ -87,38 +161,12 @@ pub fn parse_test_input_as_string(input: &str) -> String {
     dedent(input).trim().to_owned()
 }
 
-#[derive(Debug, Clone, Hash, Eq, PartialEq)]
-pub struct Point<T>
-where
-    T: Clone,
-{
-    pub x: T,
-    pub y: T,
-}
-
-impl<T> Point<T>
-where
-    T: Clone,
-{
-    pub fn new(x: T, y: T) -> Self {
-        Self { x, y }
-    }
-}
-
-impl<T> Display for Point<T>
-where
-    T: Display + Copy,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{},{}", self.x, self.y))
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
 
     use super::*;
+    use crate::testing::TempPath;
 
     #[rstest]
     fn test_parse_input() {
@@ -193,6 +241,73 @@ foobar";
         assert_eq!(expected, parse_test_input_as_string(input));
     }
 
+    #[rstest]
+    fn test_try_get_input_missing_file() {
+        let res = try_get_input("missing.txt");
+
+        assert!(matches!(res, Err(Error::MissingInput(p)) if p.ends_with("input/missing.txt")));
+    }
+
+    #[rstest]
+    fn test_get_input_as_int() {
+        let input = TempPath::with_content("ints.txt", "12\n-3\n45\n");
+
+        assert_eq!(
+            get_input_as_int::<i32>(input.path().to_str().unwrap()),
+            vec![12, -3, 45]
+        );
+    }
+
+    #[rstest]
+    #[should_panic(expected = "Invalid value on line 2: \"foo\": ParseIntError")]
+    fn test_get_input_as_int_invalid_line() {
+        let input = TempPath::with_content("ints-invalid.txt", "12\nfoo\n");
+
+        get_input_as_int::<i32>(input.path().to_str().unwrap());
+    }
+
+    /// Only needs to compile: parse errors that are only `Debug` are accepted.
+    #[allow(dead_code)]
+    fn get_input_as_int_with_debug_errors<T>(filename: &str) -> Vec<T>
+    where
+        T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Ord + FromStr,
+        <T as FromStr>::Err: Debug,
+    {
+        get_input_as_int(filename)
+    }
+
+    #[rstest]
+    fn test_parse_lines() {
+        let input = parse_test_input(
+            "
+            12
+            -3
+            45
+        ",
+        );
+
+        assert_eq!(parse_lines::<i32>(&input).unwrap(), vec![12, -3, 45]);
+    }
+
+    #[rstest]
+    fn test_parse_lines_reports_invalid_line() {
+        let input = parse_test_input(
+            "
+            12
+            foo
+            45
+        ",
+        );
+
+        let err = parse_lines::<i32>(&input).unwrap_err();
+
+        assert!(matches!(&err, Error::Parse { line: 2, text, .. } if text == "foo"));
+        assert_eq!(
+            err.to_string(),
+            "Invalid value on line 2: \"foo\": invalid digit found in string"
+        );
+    }
+
     #[rstest]
     #[case(1, "0.001μs")]
     #[case(1000, "1.000μs")]
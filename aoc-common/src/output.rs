@@ -0,0 +1,215 @@
+//! Rendering of run results, either for people to read or for other tools to consume.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::{Report, format_duration};
+
+/// How run results are rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// A block of lines per day, with the answers and durations.
+    #[default]
+    Text,
+    /// A JSON array of the reports, durations in nanoseconds.
+    Json,
+    /// Tab separated values with a header row, durations in nanoseconds.
+    Tsv,
+    /// A Markdown table, with a row per day.
+    Markdown,
+}
+
+impl Format {
+    /// Whether results can be printed as soon as each day is done, instead of all at once.
+    pub fn is_streamed(&self) -> bool {
+        matches!(self, Format::Text)
+    }
+
+    pub fn render(&self, reports: &[Report]) -> String {
+        match self {
+            Format::Text => reports.iter().map(text).collect(),
+            Format::Json => {
+                serde_json::to_string_pretty(reports).expect("reports are serializable") + "\n"
+            }
+            Format::Tsv => tsv(reports),
+            Format::Markdown => markdown(reports),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(format!(
+                "Unknown format {}, expected one of text, json, tsv or markdown",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Format::Text => "text",
+            Format::Json => "json",
+            Format::Tsv => "tsv",
+            Format::Markdown => "markdown",
+        })
+    }
+}
+
+fn text(report: &Report) -> String {
+    format!(
+        "Day {:02}\nPart 1: {}\nPart 2: {}\nDuration: {} (parse: {}, part 1: {}, part 2: {})\n",
+        report.day,
+        report.part1,
+        report.part2,
+        format_duration(report.timings.total()),
+        format_duration(report.timings.parse),
+        format_duration(report.timings.part1),
+        format_duration(report.timings.part2),
+    )
+}
+
+fn tsv(reports: &[Report]) -> String {
+    let field = |s: &str| s.replace(['\t', '\n'], " ");
+
+    let mut out = "day\tpart1\tpart2\tparse_ns\tpart1_ns\tpart2_ns\ttotal_ns\n".to_string();
+
+    for r in reports {
+        out.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            r.day,
+            field(&r.part1),
+            field(&r.part2),
+            r.timings.parse,
+            r.timings.part1,
+            r.timings.part2,
+            r.timings.total(),
+        ));
+    }
+
+    out
+}
+
+fn markdown(reports: &[Report]) -> String {
+    let cell = |s: &str| s.replace('|', "\\|").replace('\n', "<br>");
+
+    let mut out =
+        "| Day | Part 1 | Part 2 | Parse | Part 1 time | Part 2 time | Total |\n".to_string();
+    out.push_str("|----:|-------:|-------:|------:|------------:|------------:|------:|\n");
+
+    for r in reports {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} |\n",
+            r.day,
+            cell(&r.part1),
+            cell(&r.part2),
+            format_duration(r.timings.parse),
+            format_duration(r.timings.part1),
+            format_duration(r.timings.part2),
+            format_duration(r.timings.total()),
+        ));
+    }
+
+    if reports.len() > 1 {
+        let total = reports.iter().map(|r| r.timings.total()).sum();
+        out.push_str(&format!(
+            "| **Total** | | | | | | {} |\n",
+            format_duration(total)
+        ));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::Timings;
+
+    fn report(day: u8, part1: &str, part2: &str) -> Report {
+        Report {
+            day,
+            input_hash: "abc".to_string(),
+            part1: part1.to_string(),
+            part2: part2.to_string(),
+            timings: Timings {
+                parse: 1000,
+                part1: 2000,
+                part2: 3000,
+            },
+        }
+    }
+
+    #[rstest]
+    #[case("text", Format::Text)]
+    #[case("JSON", Format::Json)]
+    #[case("tsv", Format::Tsv)]
+    #[case("md", Format::Markdown)]
+    #[case("markdown", Format::Markdown)]
+    fn test_parse_format(#[case] input: &str, #[case] expected: Format) {
+        assert_eq!(input.parse::<Format>(), Ok(expected));
+    }
+
+    #[rstest]
+    fn test_parse_unknown_format() {
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[rstest]
+    fn test_text() {
+        assert_eq!(
+            Format::Text.render(&[report(1, "1141", "6634")]),
+            "Day 01\nPart 1: 1141\nPart 2: 6634\n\
+             Duration: 6.000μs (parse: 1.000μs, part 1: 2.000μs, part 2: 3.000μs)\n"
+        );
+    }
+
+    #[rstest]
+    fn test_json() {
+        let json = Format::Json.render(&[report(1, "1141", "6634")]);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value[0]["day"], 1);
+        assert_eq!(value[0]["part1"], "1141");
+        assert_eq!(value[0]["timings"]["part2"], 3000);
+    }
+
+    #[rstest]
+    fn test_tsv() {
+        let tsv = Format::Tsv.render(&[report(1, "1141", "6634"), report(2, "a\tb", "c")]);
+
+        assert_eq!(
+            tsv.lines().collect::<Vec<&str>>(),
+            vec![
+                "day\tpart1\tpart2\tparse_ns\tpart1_ns\tpart2_ns\ttotal_ns",
+                "1\t1141\t6634\t1000\t2000\t3000\t6000",
+                "2\ta b\tc\t1000\t2000\t3000\t6000",
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_markdown() {
+        let table = Format::Markdown.render(&[report(1, "1141", "6634"), report(2, "a|b", "c")]);
+        let lines = table.lines().collect::<Vec<&str>>();
+
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[2],
+            "| 1 | 1141 | 6634 | 1.000μs | 2.000μs | 3.000μs | 6.000μs |"
+        );
+        assert!(lines[3].starts_with("| 2 | a\\|b | c |"));
+        assert_eq!(lines[4], "| **Total** | | | | | | 12.000μs |");
+    }
+}
@@ -0,0 +1,707 @@
+//! Small parser combinators for the lines of puzzle inputs.
+//!
+//! A [`Parser`] reads a value from a [`Cursor`] over a line, and fails with a [`ParseError`] giving
+//! the line and column where the value was expected. Parsers are built by combining the functions
+//! of this module, then run on a whole line with [`line`] or on every line with [`lines`], or of
+//! each block of an input split on blank lines with [`split_sections`]:
+//!
+//! ```
+//! use aoc_common::parse::{self, range, separated, unsigned};
+//!
+//! let ranges = parse::line("11-22,95-115", separated(range(unsigned::<u64>()), ",")).unwrap();
+//! assert_eq!(ranges, vec![11..=22, 95..=115]);
+//! ```
+
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+pub use aoc_derive::FromLine;
+
+use crate::Point;
+
+/// Where and why a line couldn't be parsed. Lines and columns are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Solutions report parsing errors as strings.
+impl From<ParseError> for String {
+    fn from(e: ParseError) -> Self {
+        e.to_string()
+    }
+}
+
+/// The part of a line left to parse.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    text: &'a str,
+    line: usize,
+    /// Column of the start of `text` in the line, from 0.
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(text: &'a str, line: usize) -> Self {
+        Self {
+            text,
+            line,
+            offset: 0,
+        }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Column of the next character.
+    pub fn column(&self) -> usize {
+        self.offset + 1
+    }
+
+    /// An error at the current position.
+    pub fn error(&self, message: impl Display) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column(),
+            message: message.to_string(),
+        }
+    }
+
+    /// An error for something expected at the current position, describing what is there instead.
+    pub fn expected(&self, what: &str) -> ParseError {
+        match self.text.chars().next() {
+            Some(c) => self.error(format!("expected {}, found {:?}", what, c)),
+            None => self.error(format!("expected {}, found the end of the line", what)),
+        }
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let (taken, rest) = self.text.split_at(len);
+        self.text = rest;
+        self.offset += taken.chars().count();
+
+        taken
+    }
+
+    /// Consume `s` if the rest of the line starts with it.
+    pub fn eat(&mut self, s: &str) -> bool {
+        let found = self.text.starts_with(s);
+        if found {
+            self.advance(s.len());
+        }
+
+        found
+    }
+
+    /// Consume `s`, failing if the rest of the line doesn't start with it.
+    pub fn expect(&mut self, s: &str) -> Result<(), ParseError> {
+        if self.eat(s) {
+            Ok(())
+        } else {
+            Err(self.expected(&format!("{:?}", s)))
+        }
+    }
+
+    /// Consume the characters matching `pred`, returning them.
+    pub fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let len = self.text.find(|c| !pred(c)).unwrap_or(self.text.len());
+        self.advance(len)
+    }
+
+    /// Consume the next `n` characters, or the rest of the line if it is shorter.
+    pub fn take(&mut self, n: usize) -> &'a str {
+        let len = self
+            .text
+            .char_indices()
+            .nth(n)
+            .map_or(self.text.len(), |(i, _)| i);
+        self.advance(len)
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    fn end(&self) -> Result<(), ParseError> {
+        match self.text.chars().next() {
+            Some(c) => Err(self.error(format!("unexpected {:?}", c))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Reads a value from a cursor, consuming what it used.
+pub trait Parser<T> {
+    fn parse(&self, cursor: &mut Cursor<'_>) -> Result<T, ParseError>;
+}
+
+impl<T, F> Parser<T> for F
+where
+    F: Fn(&mut Cursor<'_>) -> Result<T, ParseError>,
+{
+    fn parse(&self, cursor: &mut Cursor<'_>) -> Result<T, ParseError> {
+        self(cursor)
+    }
+}
+
+/// Types read from a line, usually with `#[derive(FromLine)]` and a template of the line:
+///
+/// ```
+/// use aoc_common::parse::{self, FromLine, item};
+///
+/// #[derive(Debug, PartialEq, FromLine)]
+/// #[aoc(format = "move {n} from {from} to {to}")]
+/// struct Move {
+///     n: usize,
+///     from: u8,
+///     to: u8,
+/// }
+///
+/// let m = parse::line("move 3 from 1 to 2", item::<Move>()).unwrap();
+/// assert_eq!(m, Move { n: 3, from: 1, to: 2 });
+/// assert_eq!("move 3 from 1 to 2".parse(), Ok(m));
+/// ```
+pub trait FromLine: Sized {
+    fn parse_line(cursor: &mut Cursor<'_>) -> Result<Self, ParseError>;
+}
+
+/// A value of a [`FromLine`] type.
+pub fn item<T: FromLine>() -> impl Parser<T> {
+    parser(T::parse_line)
+}
+
+/// The value of a field of a [`FromLine`] type, from the text up to `until` or to the end of the
+/// line, with the `FromStr` implementation of its type.
+pub fn field<T>(cursor: &mut Cursor<'_>, name: &str, until: Option<&str>) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let start = cursor.clone();
+    let len = match until {
+        Some(u) => cursor.rest().find(u).ok_or_else(|| {
+            let mut end = cursor.clone();
+            end.advance(end.rest().len());
+            end.expected(&format!("{:?} after {}", u, name))
+        })?,
+        None => cursor.rest().len(),
+    };
+
+    let text = cursor.advance(len);
+    if text.is_empty() {
+        return Err(start.expected(name));
+    }
+
+    text.parse()
+        .map_err(|e| start.error(format!("invalid {}: {}", name, e)))
+}
+
+/// Gives closures the signature of a parser, which can't be inferred from the return types.
+fn parser<T, F>(f: F) -> F
+where
+    F: Fn(&mut Cursor<'_>) -> Result<T, ParseError>,
+{
+    f
+}
+
+/// Parse a whole line, numbered 1.
+pub fn line<T>(text: &str, parser: impl Parser<T>) -> Result<T, ParseError> {
+    line_at(1, text, parser)
+}
+
+/// Parse a whole line, numbered `number` in the errors.
+pub fn line_at<T>(number: usize, text: &str, parser: impl Parser<T>) -> Result<T, ParseError> {
+    within(text, number, 0, &parser)
+}
+
+/// Parse every line of the input.
+pub fn lines<T>(input: &[String], parser: impl Parser<T>) -> Result<Vec<T>, ParseError> {
+    lines_from(1, input, &parser)
+}
+
+/// Parse every line of a part of the input starting at line `first`.
+fn lines_from<T>(
+    first: usize,
+    input: &[String],
+    parser: &impl Parser<T>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .iter()
+        .enumerate()
+        .map(|(i, l)| within(l, first + i, 0, parser))
+        .collect()
+}
+
+/// A block of the input between blank lines, see [`sections`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// The number of the first line of the section in the input.
+    pub start: usize,
+    pub lines: &'a [String],
+}
+
+impl Section<'_> {
+    /// Parse every line of the section, numbered as in the whole input.
+    pub fn parse<T>(&self, parser: impl Parser<T>) -> Result<Vec<T>, ParseError> {
+        lines_from(self.start, self.lines, &parser)
+    }
+}
+
+/// Split the input into the blocks of lines separated by blank lines. Blank lines around the
+/// blocks are ignored, so a run of them separates only two blocks.
+pub fn sections(input: &[String]) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut start = None;
+
+    for (i, l) in input.iter().enumerate() {
+        match (start, l.trim().is_empty()) {
+            (None, false) => start = Some(i),
+            (Some(s), true) => {
+                sections.push(Section {
+                    start: s + 1,
+                    lines: &input[s..i],
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(s) = start {
+        sections.push(Section {
+            start: s + 1,
+            lines: &input[s..],
+        });
+    }
+
+    sections
+}
+
+/// Split the input into exactly `N` blocks of lines separated by blank lines, for inputs made of
+/// parts of different kinds:
+///
+/// ```
+/// use aoc_common::parse::{self, range, unsigned};
+/// use aoc_common::parse_test_input;
+///
+/// let input = parse_test_input(
+///     "
+///     3-5
+///     10-14
+///
+///     1
+///     5
+///     ",
+/// );
+///
+/// let [ranges, values] = parse::split_sections(&input).unwrap();
+/// assert_eq!(ranges.parse(range(unsigned::<u64>())).unwrap(), vec![3..=5, 10..=14]);
+/// assert_eq!(values.parse(unsigned::<u64>()).unwrap(), vec![1, 5]);
+/// ```
+pub fn split_sections<const N: usize>(input: &[String]) -> Result<[Section<'_>; N], ParseError> {
+    let sections = sections(input);
+    let message = format!("expected {} sections, found {}", N, sections.len());
+
+    sections.try_into().map_err(|sections: Vec<Section>| {
+        match sections.get(N) {
+            // The first section too many
+            Some(extra) => ParseError {
+                line: extra.start,
+                column: 1,
+                message,
+            },
+            // The end of the input, where the next section was expected
+            None => ParseError {
+                line: input.len().max(1),
+                column: input.last().map_or(0, |l| l.chars().count()) + 1,
+                message,
+            },
+        }
+    })
+}
+
+/// Run `parser` on `text`, a part of a line starting at column `offset` from 0, which it must
+/// consume entirely.
+fn within<T>(
+    text: &str,
+    line: usize,
+    offset: usize,
+    parser: &impl Parser<T>,
+) -> Result<T, ParseError> {
+    let mut cursor = Cursor { text, line, offset };
+
+    let value = parser.parse(&mut cursor)?;
+    cursor.end()?;
+
+    Ok(value)
+}
+
+fn number<T: FromStr>(cursor: &mut Cursor<'_>, sign: bool) -> Result<T, ParseError> {
+    let start = cursor.clone();
+
+    let mut len = 0;
+    if sign && (cursor.rest().starts_with('-') || cursor.rest().starts_with('+')) {
+        len = 1;
+    }
+    len += cursor.rest()[len..]
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(cursor.rest().len() - len);
+
+    let digits = &cursor.rest()[..len];
+    if !digits.ends_with(|c: char| c.is_ascii_digit()) {
+        return Err(start.expected("a number"));
+    }
+
+    let value = digits
+        .parse()
+        .map_err(|_| start.error(format!("number out of range: {}", digits)))?;
+    cursor.advance(len);
+
+    Ok(value)
+}
+
+/// A number made of digits only.
+pub fn unsigned<T: FromStr>() -> impl Parser<T> {
+    parser(|c| number(c, false))
+}
+
+/// A number made of digits, optionally preceded by a sign.
+pub fn signed<T: FromStr>() -> impl Parser<T> {
+    parser(|c| number(c, true))
+}
+
+/// Exactly `s`.
+pub fn literal(s: &'static str) -> impl Parser<()> {
+    parser(move |c| c.expect(s))
+}
+
+/// Two values separated by `separator`.
+pub fn pair<A, B>(
+    first: impl Parser<A>,
+    separator: &'static str,
+    second: impl Parser<B>,
+) -> impl Parser<(A, B)> {
+    parser(move |c| {
+        let a = first.parse(c)?;
+        literal(separator).parse(c)?;
+        let b = second.parse(c)?;
+
+        Ok((a, b))
+    })
+}
+
+/// Inclusive bounds separated by a dash, as in `3-5`.
+pub fn range<T>(bound: impl Parser<T>) -> impl Parser<RangeInclusive<T>> {
+    parser(move |c| {
+        let start = bound.parse(c)?;
+        literal("-").parse(c)?;
+        let end = bound.parse(c)?;
+
+        Ok(start..=end)
+    })
+}
+
+/// Coordinates separated by a comma, as in `7,1`.
+pub fn point<T: Clone>(coordinate: impl Parser<T>) -> impl Parser<Point<T>> {
+    parser(move |c| {
+        let x = coordinate.parse(c)?;
+        literal(",").parse(c)?;
+        let y = coordinate.parse(c)?;
+
+        Ok(Point::new(x, y))
+    })
+}
+
+/// At least one value, with `separator` between values.
+pub fn separated<T>(item: impl Parser<T>, separator: &'static str) -> impl Parser<Vec<T>> {
+    parser(move |c| {
+        let mut values = vec![item.parse(c)?];
+        while c.eat(separator) {
+            values.push(item.parse(c)?);
+        }
+
+        Ok(values)
+    })
+}
+
+/// Values separated by whitespace until the end of the line, ignoring leading and trailing
+/// whitespace.
+pub fn fields<T>(item: impl Parser<T>) -> impl Parser<Vec<T>> {
+    parser(move |c| {
+        let mut values = Vec::new();
+
+        c.skip_whitespace();
+        while !c.is_empty() {
+            let offset = c.offset;
+            let field = c.take_while(|ch| !ch.is_whitespace());
+            values.push(within(field, c.line, offset, &item)?);
+
+            c.skip_whitespace();
+        }
+
+        Ok(values)
+    })
+}
+
+/// A key and a value separated by `separator`, as in `name: value`. The key is the text before the
+/// separator with surrounding whitespace removed, and whitespace after the separator is skipped.
+pub fn key_value<T>(separator: &'static str, value: impl Parser<T>) -> impl Parser<(String, T)> {
+    parser(move |c| {
+        let Some(len) = c.rest().find(separator) else {
+            return Err(c.expected(&format!("a key followed by {:?}", separator)));
+        };
+
+        let key = c.take(c.rest()[..len].chars().count()).trim();
+        if key.is_empty() {
+            return Err(c.error("empty key"));
+        }
+        c.eat(separator);
+        c.skip_whitespace();
+
+        Ok((key.to_string(), value.parse(c)?))
+    })
+}
+
+/// Fields of fixed widths, as in aligned tables, with the blanks around the values ignored. The last
+/// field may be cut short by the end of the line.
+pub fn columns<T>(widths: &'static [usize], item: impl Parser<T>) -> impl Parser<Vec<T>> {
+    parser(move |c| {
+        widths
+            .iter()
+            .map(|&w| {
+                let offset = c.offset;
+                let field = c.take(w);
+                let value = field.trim_start();
+                let skipped = field.chars().count() - value.chars().count();
+
+                within(value.trim_end(), c.line, offset + skipped, &item)
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("42", Ok(42))]
+    #[case("-17", Ok(-17))]
+    #[case("+3", Ok(3))]
+    #[case("-", Err((1, "expected a number, found '-'")))]
+    #[case("4x", Err((2, "unexpected 'x'")))]
+    #[case("99999999999", Err((1, "number out of range: 99999999999")))]
+    fn test_signed(#[case] input: &str, #[case] expected: Result<i32, (usize, &str)>) {
+        let res = line(input, signed::<i32>()).map_err(|e| (e.column, e.message));
+
+        assert_eq!(res, expected.map_err(|(c, m)| (c, m.to_string())));
+    }
+
+    #[rstest]
+    fn test_unsigned_rejects_sign() {
+        let err = line("-1", unsigned::<u8>()).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Line 1, column 1: expected a number, found '-'"
+        );
+    }
+
+    #[rstest]
+    fn test_separated_ranges() {
+        let ranges = line("11-22,95-115", separated(range(unsigned::<u64>()), ",")).unwrap();
+
+        assert_eq!(ranges, vec![11..=22, 95..=115]);
+
+        let err = line("11-22,95+115", separated(range(unsigned::<u64>()), ",")).unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (9, "expected \"-\", found '+'")
+        );
+    }
+
+    #[rstest]
+    fn test_lines_of_points() {
+        let input = vec!["7,1".to_string(), "11,7".to_string(), "2;5".to_string()];
+
+        let err = lines(&input, point(unsigned::<usize>())).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError {
+                line: 3,
+                column: 2,
+                message: "expected \",\", found ';'".to_string()
+            }
+        );
+
+        let points = lines(&input[..2], point(unsigned::<usize>())).unwrap();
+        assert_eq!(points, vec![Point::new(7, 1), Point::new(11, 7)]);
+    }
+
+    #[rstest]
+    fn test_fields() {
+        assert_eq!(
+            line("  123 328  51 64 ", fields(unsigned::<u32>())).unwrap(),
+            vec![123, 328, 51, 64]
+        );
+        assert_eq!(
+            line("", fields(unsigned::<u32>())).unwrap(),
+            Vec::<u32>::new()
+        );
+
+        let err = line(" 12  3x4", fields(unsigned::<u32>())).unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (7, "unexpected 'x'"));
+    }
+
+    #[rstest]
+    fn test_key_value() {
+        let parser = || key_value(":", pair(signed::<i64>(), " ", signed::<i64>()));
+
+        assert_eq!(
+            line("Button A:  94 -34", parser()).unwrap(),
+            ("Button A".to_string(), (94, -34))
+        );
+        assert_eq!(line(": 1 2", parser()).unwrap_err().message, "empty key");
+        assert_eq!(
+            line("Prize", parser()).unwrap_err().message,
+            "expected a key followed by \":\", found 'P'"
+        );
+    }
+
+    #[rstest]
+    fn test_columns() {
+        let parser = || columns(&[4, 4, 3], unsigned::<u32>());
+
+        assert_eq!(line("123 328  51", parser()).unwrap(), vec![123, 328, 51]);
+        assert_eq!(line(" 45  64 387", parser()).unwrap(), vec![45, 64, 387]);
+
+        let err = line("  6 9x  ", parser()).unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (6, "unexpected 'x'"));
+    }
+
+    fn input(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[rstest]
+    #[case(&["a", "b", "", "c"], vec![(1, 2), (4, 1)])]
+    #[case(&["", "", "a", "", "", "b", "  ", "c", ""], vec![(3, 1), (6, 1), (8, 1)])]
+    #[case(&["a"], vec![(1, 1)])]
+    #[case(&["", " "], vec![])]
+    fn test_sections(#[case] lines: &[&str], #[case] expected: Vec<(usize, usize)>) {
+        let input = input(lines);
+
+        let res = sections(&input)
+            .iter()
+            .map(|s| (s.start, s.lines.len()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(res, expected);
+    }
+
+    #[rstest]
+    fn test_section_parse_numbers_lines_in_input() {
+        let input = input(&["1", "2", "", "3", "x"]);
+        let [first, second] = split_sections(&input).unwrap();
+
+        assert_eq!(first.parse(unsigned::<u32>()).unwrap(), vec![1, 2]);
+
+        let err = second.parse(unsigned::<u32>()).unwrap_err();
+        assert_eq!((err.line, err.column), (5, 1));
+    }
+
+    #[rstest]
+    #[case(&["1", "", "2", "", "3"], (5, 1, "expected 2 sections, found 3"))]
+    #[case(&["1", "22", ""], (3, 1, "expected 2 sections, found 1"))]
+    #[case(&["1", "22"], (2, 3, "expected 2 sections, found 1"))]
+    #[case(&[], (1, 1, "expected 2 sections, found 0"))]
+    fn test_split_sections_count(#[case] lines: &[&str], #[case] expected: (usize, usize, &str)) {
+        let input = input(lines);
+
+        let err = split_sections::<2>(&input).unwrap_err();
+
+        assert_eq!((err.line, err.column, err.message.as_str()), expected);
+    }
+
+    #[derive(Debug, PartialEq, FromLine)]
+    #[aoc(format = "{name}: {lo}..{hi} {{{tag}}}")]
+    struct Entry {
+        name: String,
+        lo: i32,
+        hi: u8,
+        tag: char,
+    }
+
+    #[rstest]
+    #[case("a b: -3..7 {x}", Ok(("a b", -3, 7, 'x')))]
+    #[case("a: 1..300 {x}", Err((7, "invalid hi: number too large to fit in target type")))]
+    #[case("a: 1..3 x", Err((10, "expected \" {\" after hi, found the end of the line")))]
+    #[case("a: ..3 {x}", Err((4, "expected lo, found '.'")))]
+    #[case("a: 1..3 {xy}", Err((10, "invalid tag: too many characters in string")))]
+    #[case("a: 1..3 {x}!", Err((12, "unexpected '!'")))]
+    fn test_derive_from_line(
+        #[case] input: &str,
+        #[case] expected: Result<(&str, i32, u8, char), (usize, &str)>,
+    ) {
+        let res = input.parse::<Entry>().map_err(|e| (e.column, e.message));
+
+        match expected {
+            Ok((name, lo, hi, tag)) => assert_eq!(
+                res,
+                Ok(Entry {
+                    name: name.to_string(),
+                    lo,
+                    hi,
+                    tag
+                })
+            ),
+            Err((column, message)) => assert_eq!(res, Err((column, message.to_string()))),
+        }
+    }
+
+    #[rstest]
+    fn test_derive_point() {
+        assert_eq!(
+            lines(
+                &["7,1".to_string(), "11,-2".to_string()],
+                item::<Point<i64>>()
+            )
+            .unwrap(),
+            vec![Point::new(7, 1), Point::new(11, -2)]
+        );
+
+        let err = line("7,-1", item::<Point<u64>>()).unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (3, "invalid y: invalid digit found in string")
+        );
+
+        let err = line("7;1", item::<Point<u64>>()).unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (4, "expected \",\" after x, found the end of the line")
+        );
+    }
+}
@@ -0,0 +1,388 @@
+use std::fmt::Display;
+use std::num::TryFromIntError;
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+
+use crate::parse::FromLine;
+
+/// A position on a 2D plane. Grids have their origin at the top left corner, with `y` growing
+/// downwards.
+///
+/// Points are read from lines written `x,y`.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, FromLine)]
+#[aoc(format = "{x},{y}")]
+pub struct Point<T>
+where
+    T: Clone,
+{
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T>
+where
+    T: Clone,
+{
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Display for Point<T>
+where
+    T: Display + Copy,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{},{}", self.x, self.y))
+    }
+}
+
+/// Integer types that can be used as coordinates, for the distance metrics.
+pub trait Coordinate:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_coordinate!(i32, i64, isize, u32, u64, usize);
+
+impl<T> Point<T>
+where
+    T: Coordinate,
+{
+    /// Distance between both coordinates, without underflowing on unsigned types.
+    pub fn abs_diff(&self, other: &Self) -> Self {
+        let diff = |a: T, b: T| if a > b { a - b } else { b - a };
+
+        Self::new(diff(self.x, other.x), diff(self.y, other.y))
+    }
+
+    /// Distance moving only horizontally and vertically.
+    pub fn manhattan(&self, other: &Self) -> T {
+        let d = self.abs_diff(other);
+        d.x + d.y
+    }
+
+    /// Distance moving in any of the 8 directions, diagonals costing as much as straight moves.
+    pub fn chebyshev(&self, other: &Self) -> T {
+        let d = self.abs_diff(other);
+        d.x.max(d.y)
+    }
+
+    /// Straight line distance.
+    pub fn euclidean(&self, other: &Self) -> f64 {
+        let d = self.abs_diff(other);
+        (d.x * d.x + d.y * d.y).to_f64().sqrt()
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Add<Output = T> + From<i8> + Copy,
+{
+    /// The adjacent point in a direction.
+    pub fn step(&self, direction: Direction) -> Self {
+        *self + direction.offset()
+    }
+}
+
+impl Point<usize> {
+    /// The adjacent point in a direction, or `None` if it would have a negative coordinate.
+    pub fn checked_step(&self, direction: Direction) -> Option<Self> {
+        let (dx, dy) = direction.delta();
+
+        Some(Self::new(
+            self.x.checked_add_signed(dx as isize)?,
+            self.y.checked_add_signed(dy as isize)?,
+        ))
+    }
+
+    /// The adjacent point in a direction, wrapping around to the other side of a `width` by
+    /// `height` area when going past its edges. The point doesn't move in an empty area.
+    pub fn wrapping_step(&self, direction: Direction, width: usize, height: usize) -> Self {
+        if width == 0 || height == 0 {
+            return *self;
+        }
+
+        let (dx, dy) = direction.delta();
+        let wrap = |v: usize, d: i8, size: usize| (v + size).wrapping_add_signed(d as isize) % size;
+
+        Self::new(wrap(self.x, dx, width), wrap(self.y, dy, height))
+    }
+}
+
+impl<T> Add for Point<T>
+where
+    T: Add<Output = T> + Clone,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T> AddAssign for Point<T>
+where
+    T: AddAssign + Clone,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T> Sub for Point<T>
+where
+    T: Sub<Output = T> + Clone,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T> SubAssign for Point<T>
+where
+    T: SubAssign + Clone,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+/// Scaling by a factor, e.g. to move several steps at once.
+impl<T> Mul<T> for Point<T>
+where
+    T: Mul<Output = T> + Clone,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs.clone(), self.y * rhs)
+    }
+}
+
+/// Coordinates are assumed to fit in an `i64`, which is the case of any grid that fits in memory.
+impl From<Point<usize>> for Point<i64> {
+    fn from(p: Point<usize>) -> Self {
+        Self::new(p.x as i64, p.y as i64)
+    }
+}
+
+/// Fails if a coordinate is negative.
+impl TryFrom<Point<i64>> for Point<usize> {
+    type Error = TryFromIntError;
+
+    fn try_from(p: Point<i64>) -> Result<Self, Self::Error> {
+        Ok(Self::new(p.x.try_into()?, p.y.try_into()?))
+    }
+}
+
+/// A direction on a grid, North being up.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The 4 directions without diagonals, clockwise from North.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The 8 directions, clockwise from North.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    fn delta(&self) -> (i8, i8) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+
+    /// The move from a point to its neighbor in this direction.
+    pub fn offset<T>(&self) -> Point<T>
+    where
+        T: From<i8> + Clone,
+    {
+        let (dx, dy) = self.delta();
+        Point::new(T::from(dx), T::from(dy))
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        let (dx, dy) = self.delta();
+        dx != 0 && dy != 0
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.rotate(4)
+    }
+
+    /// The direction 90° clockwise.
+    pub fn turn_right(&self) -> Self {
+        self.rotate(2)
+    }
+
+    /// The direction 90° counterclockwise.
+    pub fn turn_left(&self) -> Self {
+        self.rotate(6)
+    }
+
+    /// Rotate clockwise by `eighths` of a full turn.
+    fn rotate(&self, eighths: usize) -> Self {
+        let i = Self::ALL.iter().position(|d| d == self).unwrap();
+        Self::ALL[(i + eighths) % 8]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    fn test_arithmetic() {
+        let a = Point::new(3i64, 4);
+        let b = Point::new(1i64, -2);
+
+        assert_eq!(a + b, Point::new(4, 2));
+        assert_eq!(a - b, Point::new(2, 6));
+        assert_eq!(b * 3, Point::new(3, -6));
+
+        let mut c = a;
+        c += b;
+        c -= Point::new(4, 0);
+        assert_eq!(c, Point::new(0, 2));
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 0), Point::new(3, 4), 7, 4, 5.0)]
+    #[case(Point::new(3, 4), Point::new(0, 0), 7, 4, 5.0)]
+    #[case(Point::new(2, 2), Point::new(2, 2), 0, 0, 0.0)]
+    #[case(Point::new(1, 5), Point::new(2, 4), 2, 1, std::f64::consts::SQRT_2)]
+    fn test_distances(
+        #[case] a: Point<usize>,
+        #[case] b: Point<usize>,
+        #[case] manhattan: usize,
+        #[case] chebyshev: usize,
+        #[case] euclidean: f64,
+    ) {
+        assert_eq!(a.manhattan(&b), manhattan);
+        assert_eq!(a.chebyshev(&b), chebyshev);
+        assert!((a.euclidean(&b) - euclidean).abs() < 1e-9);
+    }
+
+    #[rstest]
+    fn test_distances_signed() {
+        let a = Point::new(-2i64, 3);
+        let b = Point::new(1i64, -1);
+
+        assert_eq!(a.abs_diff(&b), Point::new(3, 4));
+        assert_eq!(a.manhattan(&b), 7);
+    }
+
+    #[rstest]
+    #[case(Direction::North, Point::new(5, 4))]
+    #[case(Direction::SouthEast, Point::new(6, 6))]
+    #[case(Direction::West, Point::new(4, 5))]
+    fn test_step(#[case] direction: Direction, #[case] expected: Point<i64>) {
+        assert_eq!(Point::new(5i64, 5).step(direction), expected);
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 0), Direction::North, None)]
+    #[case(Point::new(0, 0), Direction::West, None)]
+    #[case(Point::new(0, 0), Direction::SouthEast, Some(Point::new(1, 1)))]
+    #[case(Point::new(3, 1), Direction::NorthWest, Some(Point::new(2, 0)))]
+    fn test_checked_step(
+        #[case] p: Point<usize>,
+        #[case] direction: Direction,
+        #[case] expected: Option<Point<usize>>,
+    ) {
+        assert_eq!(p.checked_step(direction), expected);
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 0), Direction::NorthWest, Point::new(4, 2))]
+    #[case(Point::new(4, 2), Direction::SouthEast, Point::new(0, 0))]
+    #[case(Point::new(2, 1), Direction::East, Point::new(3, 1))]
+    fn test_wrapping_step(
+        #[case] p: Point<usize>,
+        #[case] direction: Direction,
+        #[case] expected: Point<usize>,
+    ) {
+        assert_eq!(p.wrapping_step(direction, 5, 3), expected);
+    }
+
+    #[rstest]
+    #[case(0, 3)]
+    #[case(5, 0)]
+    fn test_wrapping_step_in_empty_area(#[case] width: usize, #[case] height: usize) {
+        let p = Point::new(1, 1);
+
+        assert_eq!(p.wrapping_step(Direction::SouthEast, width, height), p);
+    }
+
+    #[rstest]
+    fn test_directions() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::NorthEast.opposite(), Direction::SouthWest);
+        assert!(Direction::SouthWest.is_diagonal());
+        assert!(Direction::CARDINAL.iter().all(|d| !d.is_diagonal()));
+
+        let sum = Direction::ALL
+            .iter()
+            .fold(Point::new(0i64, 0), |acc, d| acc + d.offset());
+        assert_eq!(sum, Point::new(0, 0));
+    }
+
+    #[rstest]
+    fn test_conversions() {
+        let p = Point::new(3usize, 7);
+
+        assert_eq!(Point::<i64>::from(p), Point::new(3, 7));
+        assert_eq!(Point::<usize>::try_from(Point::new(3i64, 7)), Ok(p));
+        assert!(Point::<usize>::try_from(Point::new(-1i64, 7)).is_err());
+    }
+}
@@ -0,0 +1,458 @@
+//! Rendering of grids to the terminal and to images, to watch a solution at work.
+//!
+//! A [`Renderer`] colours every cell of a grid with a palette function, and can highlight some
+//! positions on top of it, such as a path or the cells that just changed. Images can be saved as
+//! PPM or PNG, and an [`Animation`] stitches them into a GIF.
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use crate::grid::Grid;
+use crate::{Error, Point};
+
+/// A colour, as its red, green and blue components.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(128, 128, 128);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(133, 153, 0);
+    pub const BLUE: Rgb = Rgb(38, 139, 210);
+    pub const YELLOW: Rgb = Rgb(181, 137, 0);
+}
+
+/// Draws grids, colouring each cell with a palette function.
+pub struct Renderer<P> {
+    palette: P,
+    highlight: HashSet<Point<usize>>,
+    highlight_color: Rgb,
+    scale: usize,
+}
+
+impl<P> Renderer<P> {
+    /// A renderer drawing every cell as a single pixel, highlighting in yellow.
+    pub fn new(palette: P) -> Self {
+        Self {
+            palette,
+            highlight: HashSet::new(),
+            highlight_color: Rgb::YELLOW,
+            scale: 1,
+        }
+    }
+
+    /// Draw every cell of the images as a square of `scale` pixels.
+    pub fn with_scale(mut self, scale: usize) -> Self {
+        assert!(scale > 0, "scale must be positive");
+        self.scale = scale;
+        self
+    }
+
+    pub fn with_highlight(mut self, positions: impl IntoIterator<Item = Point<usize>>) -> Self {
+        self.set_highlight(positions);
+        self
+    }
+
+    pub fn with_highlight_color(mut self, color: Rgb) -> Self {
+        self.highlight_color = color;
+        self
+    }
+
+    /// Replace the highlighted positions, typically between the frames of an animation.
+    pub fn set_highlight(&mut self, positions: impl IntoIterator<Item = Point<usize>>) {
+        self.highlight = positions.into_iter().collect();
+    }
+
+    fn color<T>(&self, grid: &Grid<T>, pos: Point<usize>) -> Rgb
+    where
+        P: Fn(&T) -> Rgb,
+    {
+        if self.highlight.contains(&pos) {
+            self.highlight_color
+        } else {
+            (self.palette)(&grid[pos])
+        }
+    }
+
+    /// The grid drawn with ANSI true colour escape codes, every cell as a block of two spaces so it
+    /// looks square.
+    pub fn ansi<T>(&self, grid: &Grid<T>) -> String
+    where
+        P: Fn(&T) -> Rgb,
+    {
+        self.ansi_cells(grid, |_, Rgb(r, g, b)| {
+            format!("\x1b[48;2;{};{};{}m  ", r, g, b)
+        })
+    }
+
+    /// The characters of the grid coloured with ANSI true colour escape codes, the highlighted
+    /// positions with a background of the highlight colour.
+    pub fn ansi_text<T>(&self, grid: &Grid<T>) -> String
+    where
+        T: Copy,
+        char: From<T>,
+        P: Fn(&T) -> Rgb,
+    {
+        self.ansi_cells(grid, |pos, _| {
+            let Rgb(r, g, b) = (self.palette)(&grid[pos]);
+            let c = char::from(grid[pos]);
+
+            if self.highlight.contains(&pos) {
+                let Rgb(hr, hg, hb) = self.highlight_color;
+                format!(
+                    "\x1b[38;2;{};{};{};48;2;{};{};{}m{}\x1b[49m",
+                    r, g, b, hr, hg, hb, c
+                )
+            } else {
+                format!("\x1b[38;2;{};{};{}m{}", r, g, b, c)
+            }
+        })
+    }
+
+    fn ansi_cells<T>(&self, grid: &Grid<T>, cell: impl Fn(Point<usize>, Rgb) -> String) -> String
+    where
+        P: Fn(&T) -> Rgb,
+    {
+        let mut out = String::new();
+
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                let pos = Point::new(x, y);
+                out.push_str(&cell(pos, self.color(grid, pos)));
+            }
+            let _ = writeln!(out, "\x1b[0m");
+        }
+
+        out
+    }
+
+    pub fn image<T>(&self, grid: &Grid<T>) -> Image
+    where
+        P: Fn(&T) -> Rgb,
+    {
+        let mut image = Image::new(grid.width * self.scale, grid.height * self.scale);
+
+        for pos in grid.positions() {
+            let color = self.color(grid, pos);
+
+            for dy in 0..self.scale {
+                for dx in 0..self.scale {
+                    image.set(pos.x * self.scale + dx, pos.y * self.scale + dy, color);
+                }
+            }
+        }
+
+        image
+    }
+}
+
+/// An RGB image, 8 bits per channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// A black image.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width * height * 3],
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        let i = (y * self.width + x) * 3;
+        Rgb(self.pixels[i], self.pixels[i + 1], self.pixels[i + 2])
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, Rgb(r, g, b): Rgb) {
+        let i = (y * self.width + x) * 3;
+        self.pixels[i..i + 3].copy_from_slice(&[r, g, b]);
+    }
+
+    /// The image in the binary PPM format, which most image viewers can open.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend_from_slice(&self.pixels);
+
+        ppm
+    }
+
+    pub fn save_ppm(&self, path: &Path) -> Result<(), Error> {
+        fs::write(path, self.to_ppm()).map_err(|e| write_error(path, e))
+    }
+
+    pub fn save_png(&self, path: &Path) -> Result<(), Error> {
+        let write = || -> io::Result<()> {
+            let mut encoder = png::Encoder::new(
+                BufWriter::new(File::create(path)?),
+                self.width as u32,
+                self.height as u32,
+            );
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&self.pixels)?;
+
+            Ok(writer.finish()?)
+        };
+
+        write().map_err(|e| write_error(path, e))
+    }
+}
+
+/// A sequence of images of the same size.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    frames: Vec<Image>,
+    delay: u16,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self {
+            frames: Vec::new(),
+            delay: 10,
+        }
+    }
+}
+
+impl Animation {
+    /// An animation showing each frame for 100ms.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show each frame for `delay` milliseconds, rounded down to GIF's 10ms resolution.
+    pub fn with_delay(mut self, delay: u16) -> Self {
+        self.delay = delay / 10;
+        self
+    }
+
+    /// Add a frame at the end of the animation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frame doesn't have the size of the previous ones.
+    pub fn push(&mut self, frame: Image) {
+        if let Some(first) = self.frames.first() {
+            assert_eq!(
+                (frame.width, frame.height),
+                (first.width, first.height),
+                "frames must all have the same size"
+            );
+        }
+
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Save every frame as a PNG in `dir`, numbered from `frame-0000.png`.
+    pub fn save_frames(&self, dir: &Path) -> Result<(), Error> {
+        fs::create_dir_all(dir).map_err(|e| write_error(dir, e))?;
+
+        for (i, frame) in self.frames.iter().enumerate() {
+            frame.save_png(&dir.join(format!("frame-{:04}.png", i)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Save the animation as a looping GIF. The colours are kept exact when there are at most 256
+    /// of them over all the frames, and quantized otherwise.
+    pub fn save_gif(&self, path: &Path) -> Result<(), Error> {
+        let write = || -> io::Result<()> {
+            let (width, height) = self.frames.first().map_or((0, 0), |f| (f.width, f.height));
+            let (width, height) = match (u16::try_from(width), u16::try_from(height)) {
+                (Ok(w), Ok(h)) => (w, h),
+                _ => return Err(io::Error::other("image too large for a GIF")),
+            };
+
+            let palette = self.palette();
+            let global = palette.as_ref().map_or(Vec::new(), |p| {
+                let mut colors = vec![0; p.len() * 3];
+                for (&Rgb(r, g, b), &i) in p {
+                    colors[i as usize * 3..i as usize * 3 + 3].copy_from_slice(&[r, g, b]);
+                }
+                colors
+            });
+
+            let out = BufWriter::new(File::create(path)?);
+            let mut encoder =
+                gif::Encoder::new(out, width, height, &global).map_err(io::Error::other)?;
+            encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(io::Error::other)?;
+
+            for image in &self.frames {
+                let mut frame = match &palette {
+                    Some(p) => gif::Frame {
+                        width,
+                        height,
+                        buffer: Cow::Owned(
+                            image
+                                .pixels
+                                .chunks_exact(3)
+                                .map(|c| p[&Rgb(c[0], c[1], c[2])])
+                                .collect(),
+                        ),
+                        ..gif::Frame::default()
+                    },
+                    None => gif::Frame::from_rgb_speed(width, height, &image.pixels, 10),
+                };
+                frame.delay = self.delay;
+
+                encoder.write_frame(&frame).map_err(io::Error::other)?;
+            }
+
+            Ok(())
+        };
+
+        write().map_err(|e| write_error(path, e))
+    }
+
+    /// The index of every colour used in the frames, if there are few enough for a GIF palette.
+    fn palette(&self) -> Option<HashMap<Rgb, u8>> {
+        let mut palette = HashMap::new();
+
+        for image in &self.frames {
+            for c in image.pixels.chunks_exact(3) {
+                let color = Rgb(c[0], c[1], c[2]);
+                if !palette.contains_key(&color) {
+                    let i = u8::try_from(palette.len()).ok()?;
+                    palette.insert(color, i);
+                }
+            }
+        }
+
+        Some(palette)
+    }
+}
+
+/// Error for a file that couldn't be written. Unlike for inputs, a missing directory is just an IO
+/// error.
+fn write_error(path: &Path, source: io::Error) -> Error {
+    Error::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::{fixture, rstest};
+
+    use super::*;
+    use crate::parse_test_input;
+    use crate::testing::TempPath;
+
+    #[fixture]
+    fn grid() -> Grid<char> {
+        Grid::from_input(&parse_test_input(
+            "
+            #.
+            .#
+            ",
+        ))
+        .unwrap()
+    }
+
+    fn palette(c: &char) -> Rgb {
+        match c {
+            '#' => Rgb::WHITE,
+            _ => Rgb::BLACK,
+        }
+    }
+
+    #[rstest]
+    fn test_image(grid: Grid<char>) {
+        let image = Renderer::new(palette)
+            .with_scale(2)
+            .with_highlight([Point::new(1, 0)])
+            .with_highlight_color(Rgb::RED)
+            .image(&grid);
+
+        assert_eq!((image.width, image.height), (4, 4));
+        assert_eq!(image.get(1, 1), Rgb::WHITE);
+        assert_eq!(image.get(2, 0), Rgb::RED);
+        assert_eq!(image.get(3, 1), Rgb::RED);
+        assert_eq!(image.get(0, 3), Rgb::BLACK);
+        assert_eq!(image.get(3, 3), Rgb::WHITE);
+    }
+
+    #[rstest]
+    fn test_ppm(grid: Grid<char>) {
+        let ppm = Renderer::new(palette).image(&grid).to_ppm();
+
+        let (header, pixels) = ppm.split_at(11);
+        assert_eq!(header, b"P6\n2 2\n255\n");
+        assert_eq!(pixels, &[255, 255, 255, 0, 0, 0, 0, 0, 0, 255, 255, 255]);
+    }
+
+    #[rstest]
+    fn test_ansi(grid: Grid<char>) {
+        let renderer = Renderer::new(palette).with_highlight([Point::new(0, 0)]);
+
+        assert_eq!(
+            renderer.ansi(&grid).lines().next(),
+            Some("\x1b[48;2;181;137;0m  \x1b[48;2;0;0;0m  \x1b[0m")
+        );
+        assert_eq!(
+            renderer.ansi_text(&grid).lines().nth(1),
+            Some("\x1b[38;2;0;0;0m.\x1b[38;2;255;255;255m#\x1b[0m")
+        );
+    }
+
+    #[rstest]
+    fn test_save(grid: Grid<char>) {
+        let mut renderer = Renderer::new(palette);
+        let mut animation = Animation::new().with_delay(50);
+        for p in grid.positions() {
+            renderer.set_highlight([p]);
+            animation.push(renderer.image(&grid));
+        }
+
+        let temp = TempPath::new("render");
+        let dir = temp.path();
+        animation.save_frames(dir).unwrap();
+        animation.save_gif(&dir.join("animation.gif")).unwrap();
+
+        let png = fs::read(dir.join("frame-0003.png")).unwrap();
+        let gif = fs::read(dir.join("animation.gif")).unwrap();
+
+        assert_eq!(animation.len(), 4);
+        assert_eq!(animation.palette().map(|p| p.len()), Some(3));
+        assert!(png.starts_with(b"\x89PNG"));
+        assert!(gif.starts_with(b"GIF89a"));
+    }
+
+    #[rstest]
+    #[should_panic(expected = "frames must all have the same size")]
+    fn test_frames_of_different_sizes() {
+        let mut animation = Animation::new();
+        animation.push(Image::new(2, 2));
+        animation.push(Image::new(3, 2));
+    }
+}
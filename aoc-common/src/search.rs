@@ -0,0 +1,456 @@
+//! Searches over graphs given by a successor function, so they work the same on grids and on
+//! graphs that are never built explicitly.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::Direction;
+use super::grid::GridLike;
+
+/// The nodes reached by a search, with their cost from the start and how they were reached.
+///
+/// A search stops as soon as it finds a goal, in which case the costs of the nodes that were
+/// reached but not yet explored may not be the lowest ones.
+#[derive(Debug, Clone)]
+pub struct SearchResult<N, C> {
+    start: N,
+    goal: Option<N>,
+    costs: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+impl<N, C> SearchResult<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy,
+{
+    /// The goal that was found, if any.
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// The cost of the path to the goal.
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal.as_ref().and_then(|g| self.cost(g))
+    }
+
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// Every node reached, with its cost.
+    pub fn reached(&self) -> impl Iterator<Item = (&N, &C)> {
+        self.costs.iter()
+    }
+
+    /// The nodes from the start to the goal, both included.
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.goal.as_ref().and_then(|g| self.path_to(g))
+    }
+
+    /// The nodes from the start to a reached node, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.costs.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        let mut cur = node;
+
+        while *cur != self.start {
+            cur = &self.parents[cur];
+            path.push(cur.clone());
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search, for graphs where every move costs the same. Explores the whole reachable
+/// graph if `is_goal` never matches.
+pub fn bfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::from([start.clone()]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return SearchResult {
+                start,
+                goal: Some(node),
+                costs,
+                parents,
+            };
+        }
+
+        let cost = costs[&node] + 1;
+
+        for next in successors(&node) {
+            if !costs.contains_key(&next) {
+                costs.insert(next.clone(), cost);
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    SearchResult {
+        start,
+        goal: None,
+        costs,
+        parents,
+    }
+}
+
+/// Lowest cost search, for graphs where moves have different costs, given with each successor.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Lowest cost search guided by `heuristic`, an estimate of the cost from a node to the goal. The
+/// path found is the cheapest as long as the heuristic never overestimates.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> SearchResult<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs = HashMap::from([(start.clone(), C::default())]);
+    let mut parents = HashMap::new();
+    let mut queue = BinaryHeap::from([Entry {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start.clone(),
+    }]);
+
+    while let Some(Entry { cost, node, .. }) = queue.pop() {
+        // A cheaper way to this node was found after it was queued
+        if costs.get(&node).is_some_and(|&c| c < cost) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return SearchResult {
+                start,
+                goal: Some(node),
+                costs,
+                parents,
+            };
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+
+            if costs.get(&next).is_none_or(|&c| next_cost < c) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                queue.push(Entry {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    SearchResult {
+        start,
+        goal: None,
+        costs,
+        parents,
+    }
+}
+
+/// Number of distinct paths from `start` to the goals, in a graph without cycles. Paths stop at
+/// the first goal they reach.
+pub fn count_paths<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> usize
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    fn count<N, I>(
+        node: N,
+        successors: &mut impl FnMut(&N) -> I,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        cache: &mut HashMap<N, usize>,
+    ) -> usize
+    where
+        N: Eq + Hash + Clone,
+        I: IntoIterator<Item = N>,
+    {
+        if let Some(&n) = cache.get(&node) {
+            return n;
+        }
+
+        let n = if is_goal(&node) {
+            1
+        } else {
+            successors(&node)
+                .into_iter()
+                .collect::<Vec<N>>()
+                .into_iter()
+                .map(|next| count(next, successors, is_goal, cache))
+                .sum()
+        };
+
+        cache.insert(node, n);
+        n
+    }
+
+    count(start, &mut successors, &mut is_goal, &mut HashMap::new())
+}
+
+/// An entry of the priority queue, ordered so the lowest priority is popped first.
+struct Entry<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+/// A grid seen as a graph, whose nodes are the passable cells, connected to their neighbors.
+pub struct GridGraph<'a, G, F> {
+    grid: &'a G,
+    passable: F,
+    directions: &'static [Direction],
+}
+
+impl<'a, G, F> GridGraph<'a, G, F>
+where
+    G: GridLike,
+    F: Fn(&G::Value) -> bool,
+{
+    /// A graph moving up, down, left and right between cells whose value matches `passable`.
+    pub fn new(grid: &'a G, passable: F) -> Self {
+        Self {
+            grid,
+            passable,
+            directions: &Direction::CARDINAL,
+        }
+    }
+
+    /// Also move diagonally.
+    pub fn with_diagonals(mut self) -> Self {
+        self.directions = &Direction::ALL;
+        self
+    }
+
+    /// The passable cells next to a position.
+    pub fn successors<'s>(
+        &'s self,
+        pos: &G::Position,
+    ) -> impl Iterator<Item = G::Position> + use<'s, 'a, G, F> {
+        self.grid
+            .adjacent(pos, self.directions)
+            .filter(|n| self.grid.value(n).is_some_and(|v| (self.passable)(v)))
+    }
+
+    /// Shortest paths from `start`, stopping at the first position matching `is_goal`.
+    pub fn bfs(
+        &self,
+        start: G::Position,
+        is_goal: impl FnMut(&G::Position) -> bool,
+    ) -> SearchResult<G::Position, usize> {
+        bfs(start, |p| self.successors(p), is_goal)
+    }
+
+    /// Cheapest paths from `start`, `cost` giving the cost of moving from a cell to the next.
+    pub fn dijkstra<C>(
+        &self,
+        start: G::Position,
+        cost: impl Fn(&G::Position, &G::Position) -> C,
+        is_goal: impl FnMut(&G::Position) -> bool,
+    ) -> SearchResult<G::Position, C>
+    where
+        C: Ord + Copy + Add<Output = C> + Default,
+    {
+        let cost = &cost;
+
+        dijkstra(
+            start,
+            |p| {
+                let p = *p;
+                self.successors(&p).map(move |n| (n, cost(&p, &n)))
+            },
+            is_goal,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::{fixture, rstest};
+
+    use super::*;
+    use crate::grid::Grid;
+    use crate::{Point, parse_test_input};
+
+    #[fixture]
+    fn maze() -> Grid<char> {
+        Grid::from_input(&parse_test_input(
+            "
+            S.#.....
+            .##.###.
+            ....#...
+            .##...#E
+            ",
+        ))
+        .unwrap()
+    }
+
+    /// Weighted graph with a cheap long path and an expensive short one.
+    fn weighted(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 1), ('d', 10)],
+            'b' => vec![('c', 1)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[rstest]
+    fn test_bfs(maze: Grid<char>) {
+        let start = maze.find(&'S').unwrap();
+        let end = maze.find(&'E').unwrap();
+
+        let res = GridGraph::new(&maze, |&c| c != '#').bfs(start, |p| *p == end);
+
+        assert_eq!(res.goal(), Some(&end));
+        assert_eq!(res.goal_cost(), Some(12));
+
+        let path = res.path().unwrap();
+        assert_eq!(path.len(), 13);
+        assert_eq!(path[0], start);
+        assert_eq!(path[12], end);
+        assert!(
+            path.windows(2)
+                .all(|w| w[0].manhattan(&w[1]) == 1 && maze[w[1]] != '#')
+        );
+    }
+
+    #[rstest]
+    fn test_bfs_with_diagonals(maze: Grid<char>) {
+        let start = maze.find(&'S').unwrap();
+        let end = maze.find(&'E').unwrap();
+
+        let res = GridGraph::new(&maze, |&c| c != '#')
+            .with_diagonals()
+            .bfs(start, |p| *p == end);
+
+        assert_eq!(res.goal_cost(), Some(8));
+    }
+
+    #[rstest]
+    fn test_bfs_unreachable(maze: Grid<char>) {
+        let res = GridGraph::new(&maze, |&c| c == '.' || c == 'S')
+            .bfs(Point::new(0, 0), |p| *p == Point::new(7, 3));
+
+        assert_eq!(res.goal(), None);
+        assert_eq!(res.path(), None);
+        assert_eq!(res.cost(&Point::new(3, 2)), Some(5));
+        assert_eq!(res.cost(&Point::new(3, 0)), Some(7));
+        assert_eq!(res.reached().count(), 21);
+    }
+
+    #[rstest]
+    fn test_dijkstra() {
+        let res = dijkstra('a', weighted, |&n| n == 'd');
+
+        assert_eq!(res.goal_cost(), Some(3));
+        assert_eq!(res.path(), Some(vec!['a', 'b', 'c', 'd']));
+        assert_eq!(res.path_to(&'b'), Some(vec!['a', 'b']));
+        assert_eq!(res.path_to(&'z'), None);
+    }
+
+    #[rstest]
+    fn test_grid_dijkstra(maze: Grid<char>) {
+        let start = maze.find(&'S').unwrap();
+        let end = maze.find(&'E').unwrap();
+
+        // Moving down costs 5
+        let res = GridGraph::new(&maze, |&c| c != '#').dijkstra(
+            start,
+            |a, b| if b.y > a.y { 5 } else { 1 },
+            |p| *p == end,
+        );
+
+        assert_eq!(res.goal_cost(), Some(28));
+    }
+
+    #[rstest]
+    fn test_astar(maze: Grid<char>) {
+        let start = maze.find(&'S').unwrap();
+        let end = maze.find(&'E').unwrap();
+        let graph = GridGraph::new(&maze, |&c| c != '#');
+
+        let res = astar(
+            start,
+            |p| graph.successors(p).map(|n| (n, 1)).collect::<Vec<_>>(),
+            |p| p.manhattan(&end),
+            |p| *p == end,
+        );
+
+        assert_eq!(res.goal_cost(), Some(12));
+        assert!(res.reached().count() <= graph.bfs(start, |p| *p == end).reached().count());
+    }
+
+    #[rstest]
+    fn test_count_paths() {
+        // Every node splits in two until depth 3
+        let n = count_paths(
+            (0, 0),
+            |&(depth, i)| [(depth + 1, i), (depth + 1, i + 1)],
+            |&(depth, _)| depth == 3,
+        );
+
+        assert_eq!(n, 8);
+    }
+}
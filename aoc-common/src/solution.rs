@@ -0,0 +1,311 @@
+use std::fmt::Display;
+use std::hint::black_box;
+use std::time::Instant;
+
+use serde::Serialize;
+
+use super::bench::{BenchOptions, BenchReport, Stats};
+use super::fetch::content_hash;
+use super::render::Animation;
+use super::viewer::Recording;
+use super::{Error, InputSource};
+
+/// Puzzle input that can be loaded from an [`InputSource`].
+pub trait PuzzleInput: Sized {
+    fn load(source: &InputSource) -> Result<Self, Error>;
+
+    /// Identifies the input, as the hash of its content with surrounding whitespace removed.
+    fn hash(&self) -> String;
+}
+
+impl PuzzleInput for Vec<String> {
+    fn load(source: &InputSource) -> Result<Self, Error> {
+        source.read_lines()
+    }
+
+    fn hash(&self) -> String {
+        content_hash(self.join("\n").trim())
+    }
+}
+
+impl PuzzleInput for String {
+    fn load(source: &InputSource) -> Result<Self, Error> {
+        source.read_to_string()
+    }
+
+    fn hash(&self) -> String {
+        content_hash(self.trim())
+    }
+}
+
+/// A day's solution. Each day implements this trait on a unit struct, which is then registered
+/// with the runner.
+///
+/// Parsing and both parts are separate stages so they can be timed and tested independently.
+pub trait Solution {
+    /// The day of the puzzle.
+    const DAY: u8;
+
+    /// How the puzzle input is loaded, either as a list of lines or as a single string.
+    type Input: PuzzleInput;
+
+    /// The parsed puzzle input, shared by both parts.
+    type Parsed;
+
+    fn parse(input: &Self::Input) -> Result<Self::Parsed, String>;
+
+    fn part1(parsed: &Self::Parsed) -> impl Display;
+
+    fn part2(parsed: &Self::Parsed) -> impl Display;
+
+    /// Frames showing the solution at work, for the days that have a visualisation.
+    fn animate(_parsed: &Self::Parsed) -> Option<Animation> {
+        None
+    }
+
+    /// Load the puzzle input, parse it and solve both parts, timing each stage separately.
+    fn run(source: &InputSource) -> Result<Report, Error> {
+        let _span = tracing::info_span!("solve", day = Self::DAY).entered();
+
+        let input = Self::Input::load(source)?;
+
+        let (parsed, parse) = timed(|| Self::parse(&input));
+        let parsed = parsed.map_err(Error::InvalidInput)?;
+
+        let (r1, part1) = timed(|| Self::part1(&parsed));
+        let (r2, part2) = timed(|| Self::part2(&parsed));
+
+        Ok(Report {
+            day: Self::DAY,
+            input_hash: input.hash(),
+            part1: r1.to_string(),
+            part2: r2.to_string(),
+            timings: Timings {
+                parse,
+                part1,
+                part2,
+            },
+        })
+    }
+
+    /// Load the puzzle input once, then parse it and solve both parts repeatedly, collecting
+    /// statistics on the duration of each stage.
+    fn bench(source: &InputSource, options: &BenchOptions) -> Result<BenchReport, Error> {
+        let input = Self::Input::load(source)?;
+
+        let mut samples: [Vec<u128>; 3] = Default::default();
+
+        for i in 0..options.warmup + options.iterations {
+            let (parsed, parse) = timed(|| Self::parse(black_box(&input)));
+            let parsed = parsed.map_err(Error::InvalidInput)?;
+
+            let (_, part1) = timed(|| black_box(Self::part1(&parsed)));
+            let (_, part2) = timed(|| black_box(Self::part2(&parsed)));
+
+            if i >= options.warmup {
+                for (s, t) in samples.iter_mut().zip([parse, part1, part2]) {
+                    s.push(t);
+                }
+            }
+        }
+
+        let [parse, part1, part2] = samples.map(|s| Stats::from_samples(&s));
+
+        Ok(BenchReport {
+            day: Self::DAY,
+            iterations: options.iterations,
+            parse,
+            part1,
+            part2,
+        })
+    }
+
+    /// Load and parse the puzzle input, then animate the solution.
+    fn render(source: &InputSource) -> Result<Option<Animation>, Error> {
+        let input = Self::Input::load(source)?;
+        let parsed = Self::parse(&input).map_err(Error::InvalidInput)?;
+
+        Ok(Self::animate(&parsed))
+    }
+
+    /// Load and parse the puzzle input, then solve both parts, keeping the steps they record with
+    /// [`crate::viewer::record`].
+    fn record(source: &InputSource) -> Result<Recording, Error> {
+        let input = Self::Input::load(source)?;
+        let parsed = Self::parse(&input).map_err(Error::InvalidInput)?;
+
+        let (_, recording) = Recording::capture(|| {
+            Self::part1(&parsed).to_string();
+            Self::part2(&parsed).to_string();
+        });
+
+        Ok(recording)
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, u128) {
+    let start = Instant::now();
+
+    let res = f();
+
+    (res, start.elapsed().as_nanos())
+}
+
+/// The answers of a solution run, along with how long each stage took to compute.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub day: u8,
+    pub input_hash: String,
+    pub part1: String,
+    pub part2: String,
+    pub timings: Timings,
+}
+
+/// Durations of each stage of a solution run, in nanoseconds.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Timings {
+    pub parse: u128,
+    pub part1: u128,
+    pub part2: u128,
+}
+
+impl Timings {
+    pub fn total(&self) -> u128 {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// Object safe view of a [`Solution`], allowing the runner to hold every day in a single list.
+pub trait Runnable: Sync {
+    fn day(&self) -> u8;
+
+    fn run(&self, source: &InputSource) -> Result<Report, Error>;
+
+    fn bench(&self, source: &InputSource, options: &BenchOptions) -> Result<BenchReport, Error>;
+
+    fn render(&self, source: &InputSource) -> Result<Option<Animation>, Error>;
+
+    fn record(&self, source: &InputSource) -> Result<Recording, Error>;
+}
+
+impl<S> Runnable for S
+where
+    S: Solution + Sync,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn run(&self, source: &InputSource) -> Result<Report, Error> {
+        S::run(source)
+    }
+
+    fn bench(&self, source: &InputSource, options: &BenchOptions) -> Result<BenchReport, Error> {
+        S::bench(source, options)
+    }
+
+    fn render(&self, source: &InputSource) -> Result<Option<Animation>, Error> {
+        S::render(source)
+    }
+
+    fn record(&self, source: &InputSource) -> Result<Recording, Error> {
+        S::record(source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::testing::TempPath;
+    use crate::{parse_test_input, parse_test_input_as_string};
+
+    #[rstest]
+    fn test_input_hash_does_not_depend_on_loader() {
+        let input = "
+            3-5
+            10-14
+
+            1
+            5
+        ";
+
+        let lines = parse_test_input(input);
+        let string = parse_test_input_as_string(input);
+
+        assert_eq!(lines.hash(), string.hash());
+        assert_eq!(vec![String::new()].hash(), String::new().hash());
+    }
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 1;
+        type Input = Vec<String>;
+        type Parsed = Vec<u32>;
+
+        fn parse(input: &Vec<String>) -> Result<Vec<u32>, String> {
+            input
+                .iter()
+                .map(|l| l.parse().map_err(|_| format!("Invalid number: {}", l)))
+                .collect()
+        }
+
+        fn part1(values: &Vec<u32>) -> impl Display {
+            values.iter().sum::<u32>()
+        }
+
+        fn part2(values: &Vec<u32>) -> impl Display {
+            values.iter().max().copied().unwrap_or_default()
+        }
+    }
+
+    fn source(input: &TempPath) -> InputSource {
+        InputSource::File(input.path().to_path_buf())
+    }
+
+    #[rstest]
+    fn test_run() {
+        let input = TempPath::with_content("run.txt", "1\n5\n3\n");
+        let report = <Sum as Solution>::run(&source(&input)).unwrap();
+
+        assert_eq!(report.day, 1);
+        assert_eq!(report.part1, "9");
+        assert_eq!(report.part2, "5");
+        assert_eq!(report.input_hash, content_hash("1\n5\n3"));
+    }
+
+    #[rstest]
+    fn test_run_invalid_input() {
+        let input = TempPath::with_content("run-invalid.txt", "1\nfoo\n");
+        let res = <Sum as Solution>::run(&source(&input));
+
+        assert!(matches!(res, Err(Error::InvalidInput(m)) if m == "Invalid number: foo"));
+    }
+
+    #[rstest]
+    fn test_render_without_animation() {
+        let input = TempPath::with_content("render.txt", "1\n5\n3\n");
+        let animation = <Sum as Solution>::render(&source(&input)).unwrap();
+
+        assert!(animation.is_none());
+    }
+
+    #[rstest]
+    fn test_bench() {
+        let options = BenchOptions {
+            warmup: 2,
+            iterations: 5,
+        };
+
+        let input = TempPath::with_content("bench.txt", "1\n5\n3\n");
+        let report = <Sum as Solution>::bench(&source(&input), &options).unwrap();
+
+        assert_eq!(report.day, 1);
+        assert_eq!(report.iterations, 5);
+        for (_, s) in report.stages() {
+            assert!(s.min <= s.median);
+        }
+    }
+}
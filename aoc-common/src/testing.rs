@@ -0,0 +1,44 @@
+//! Helpers shared by the tests of this crate.
+
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+
+/// A file or directory in the temporary directory, named after a test and the process so that
+/// concurrent runs don't share it. Nothing is created, but whatever is at the path is removed
+/// before and after the test.
+pub struct TempPath(PathBuf);
+
+impl TempPath {
+    pub fn new(name: &str) -> Self {
+        let temp = Self(env::temp_dir().join(format!("aoc-{}-{}", name, process::id())));
+        temp.remove();
+
+        temp
+    }
+
+    /// A temporary file with some content.
+    pub fn with_content(name: &str, content: &str) -> Self {
+        let temp = Self::new(name);
+        fs::write(temp.path(), content).unwrap();
+
+        temp
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    fn remove(&self) {
+        if self.0.is_dir() {
+            let _ = fs::remove_dir_all(&self.0);
+        } else {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
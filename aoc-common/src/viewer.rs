@@ -0,0 +1,431 @@
+//! Step-through viewer for grid simulations.
+//!
+//! Solvers call [`record`] after each step of a simulation. It does nothing unless the solver runs
+//! within [`Recording::capture`], so the calls can stay in the solutions. The recorded steps can
+//! then be replayed in the terminal with a [`Viewer`].
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::io::{self, Write};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::{Attribute, Color, Print, SetAttribute, SetBackgroundColor};
+use crossterm::{cursor, queue, terminal};
+
+use crate::Point;
+use crate::grid::Grid;
+
+thread_local! {
+    static RECORDING: RefCell<Option<Vec<Step>>> = const { RefCell::new(None) };
+}
+
+/// Whether the steps recorded on this thread are kept, to skip preparing them when they aren't.
+pub fn is_recording() -> bool {
+    RECORDING.with_borrow(Option::is_some)
+}
+
+/// Record the state of a grid after a step of a simulation.
+pub fn record<T>(grid: &Grid<T>, label: impl Display)
+where
+    T: Copy,
+    char: From<T>,
+{
+    record_with_highlight(grid, label, []);
+}
+
+/// Record the state of a grid after a step of a simulation, with some positions of interest.
+pub fn record_with_highlight<T>(
+    grid: &Grid<T>,
+    label: impl Display,
+    highlight: impl IntoIterator<Item = Point<usize>>,
+) where
+    T: Copy,
+    char: From<T>,
+{
+    RECORDING.with_borrow_mut(|recording| {
+        if let Some(steps) = recording {
+            steps.push(Step {
+                label: label.to_string(),
+                grid: grid.map(|&v| char::from(v)),
+                highlight: highlight.into_iter().collect(),
+            });
+        }
+    });
+}
+
+/// A recorded state of a grid.
+#[derive(Clone)]
+pub struct Step {
+    pub label: String,
+    pub grid: Grid<char>,
+    pub highlight: Vec<Point<usize>>,
+}
+
+/// The steps recorded while running a solver.
+#[derive(Clone, Default)]
+pub struct Recording {
+    steps: Vec<Step>,
+}
+
+impl Recording {
+    /// Run `f`, keeping the steps it records on this thread.
+    pub fn capture<R>(f: impl FnOnce() -> R) -> (R, Recording) {
+        let previous = RECORDING.with_borrow_mut(|r| r.replace(Vec::new()));
+
+        let res = f();
+
+        let steps = RECORDING.with_borrow_mut(|r| std::mem::replace(r, previous));
+
+        (
+            res,
+            Recording {
+                steps: steps.unwrap_or_default(),
+            },
+        )
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+/// Width of the inspector panel, on the right of the grid.
+const PANEL_WIDTH: u16 = 24;
+
+const HELP: &str = "←/→ step  Home/End first/last  <n>⏎ jump  hjkl move  q quit";
+
+/// Interactive replay of a [`Recording`] in the terminal, with an inspector showing the cell under
+/// the cursor and its neighbors.
+pub struct Viewer {
+    recording: Recording,
+    step: usize,
+    cursor: Point<usize>,
+    /// Step number being typed, to jump to it.
+    jump: String,
+}
+
+impl Viewer {
+    /// A viewer on the first step of a recording, or `None` if no step was recorded.
+    pub fn new(recording: Recording) -> Option<Self> {
+        if recording.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            recording,
+            step: 0,
+            cursor: Point::new(0, 0),
+            jump: String::new(),
+        })
+    }
+
+    /// Index of the step shown.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    pub fn cursor(&self) -> Point<usize> {
+        self.cursor
+    }
+
+    fn current(&self) -> &Step {
+        &self.recording.steps[self.step]
+    }
+
+    fn go_to(&mut self, step: usize) {
+        self.step = step.min(self.recording.len() - 1);
+
+        // Grids can change size between steps
+        let grid = &self.current().grid;
+        self.cursor = Point::new(
+            self.cursor.x.min(grid.width.saturating_sub(1)),
+            self.cursor.y.min(grid.height.saturating_sub(1)),
+        );
+    }
+
+    fn move_cursor(&mut self, dx: isize, dy: isize) {
+        let grid = &self.current().grid;
+
+        if let (Some(x), Some(y)) = (
+            self.cursor.x.checked_add_signed(dx),
+            self.cursor.y.checked_add_signed(dy),
+        ) && grid.contains(&Point::new(x, y))
+        {
+            self.cursor = Point::new(x, y);
+        }
+    }
+
+    /// Update the state for a key press, returning whether the viewer should keep going.
+    pub fn handle(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char(c @ '0'..='9') => self.jump.push(c),
+            KeyCode::Backspace => {
+                self.jump.pop();
+            }
+            // Steps are numbered from 1 on screen
+            KeyCode::Enter => {
+                if let Ok(n) = self.jump.parse::<usize>() {
+                    self.go_to(n.saturating_sub(1));
+                }
+                self.jump.clear();
+            }
+            KeyCode::Right | KeyCode::Char('n') => self.go_to(self.step + 1),
+            KeyCode::Left | KeyCode::Char('p') => self.go_to(self.step.saturating_sub(1)),
+            KeyCode::Home => self.go_to(0),
+            KeyCode::End => self.go_to(self.recording.len() - 1),
+            KeyCode::Char('h') => self.move_cursor(-1, 0),
+            KeyCode::Char('j') => self.move_cursor(0, 1),
+            KeyCode::Char('k') => self.move_cursor(0, -1),
+            KeyCode::Char('l') => self.move_cursor(1, 0),
+            _ => {}
+        }
+
+        true
+    }
+
+    /// The lines of the inspector: the position and value of the cell under the cursor, and the
+    /// cells around it, blank outside of the grid.
+    pub fn inspect(&self) -> Vec<String> {
+        let step = self.current();
+        let grid = &step.grid;
+        let p = self.cursor;
+
+        let mut lines = vec![format!("Cell ({}, {})", p.x, p.y)];
+        if !grid.contains(&p) {
+            return lines;
+        }
+
+        lines.push(format!("Value {:?}", grid[p]));
+        if step.highlight.contains(&p) {
+            lines.push("Highlighted".to_string());
+        }
+        lines.push("Neighbors".to_string());
+
+        let around = |dx, dy| match (p.x.checked_add_signed(dx), p.y.checked_add_signed(dy)) {
+            (Some(x), Some(y)) if grid.contains(&Point::new(x, y)) => grid[Point::new(x, y)],
+            _ => ' ',
+        };
+        for dy in -1..=1 {
+            let row = (-1..=1)
+                .map(|dx| around(dx, dy).to_string())
+                .collect::<Vec<_>>();
+            lines.push(format!("  {}", row.join(" ")));
+        }
+
+        lines
+    }
+
+    /// Replay the recording until the user quits.
+    pub fn run(&mut self) -> io::Result<()> {
+        let mut out = io::stdout();
+        let _terminal = RawTerminal::enter(&mut out)?;
+
+        loop {
+            self.draw(&mut out, terminal::size()?)?;
+
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+                && !self.handle(key.code)
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    fn draw(&self, out: &mut impl Write, (cols, rows): (u16, u16)) -> io::Result<()> {
+        let step = self.current();
+        let grid = &step.grid;
+
+        queue!(
+            out,
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(0, 0),
+            SetAttribute(Attribute::Bold),
+            Print(format!(
+                "Step {}/{}: {}",
+                self.step + 1,
+                self.recording.len(),
+                step.label
+            )),
+            SetAttribute(Attribute::Reset),
+        )?;
+
+        // Part of the grid that fits, scrolled to keep the cursor in the middle when it's large
+        let view_width = cols.saturating_sub(PANEL_WIDTH + 1) as usize;
+        let view_height = rows.saturating_sub(3) as usize;
+        let offset = |cursor: usize, size: usize, view: usize| {
+            cursor
+                .saturating_sub(view / 2)
+                .min(size.saturating_sub(view))
+        };
+        let (x0, y0) = (
+            offset(self.cursor.x, grid.width, view_width),
+            offset(self.cursor.y, grid.height, view_height),
+        );
+
+        for y in y0..grid.height.min(y0 + view_height) {
+            queue!(out, cursor::MoveTo(0, (y - y0 + 1) as u16))?;
+
+            for x in x0..grid.width.min(x0 + view_width) {
+                let p = Point::new(x, y);
+
+                if p == self.cursor {
+                    queue!(out, SetAttribute(Attribute::Reverse))?;
+                } else if step.highlight.contains(&p) {
+                    queue!(out, SetBackgroundColor(Color::DarkYellow))?;
+                }
+                queue!(out, Print(grid[p]), SetAttribute(Attribute::Reset))?;
+            }
+        }
+
+        let panel = cols.saturating_sub(PANEL_WIDTH);
+        for (i, line) in self.inspect().iter().enumerate() {
+            queue!(out, cursor::MoveTo(panel, i as u16 + 1), Print(line))?;
+        }
+
+        let status = if self.jump.is_empty() {
+            HELP.to_string()
+        } else {
+            format!("Jump to step {}", self.jump)
+        };
+        queue!(
+            out,
+            cursor::MoveTo(0, rows.saturating_sub(1)),
+            Print(status)
+        )?;
+
+        out.flush()
+    }
+}
+
+/// Raw mode on the alternate screen, restored when dropped, even on errors.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        crossterm::execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        Ok(RawTerminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = crossterm::execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::{fixture, rstest};
+
+    use super::*;
+    use crate::parse_test_input;
+
+    #[fixture]
+    fn recording() -> Recording {
+        let mut grid = Grid::<char>::from_input(&parse_test_input(
+            "
+            #..
+            ...
+            ",
+        ))
+        .unwrap();
+
+        let (_, recording) = Recording::capture(|| {
+            for x in 1..3 {
+                grid[Point::new(x, 0)] = '#';
+                record_with_highlight(&grid, format!("x={}", x), [Point::new(x, 0)]);
+            }
+        });
+
+        recording
+    }
+
+    #[rstest]
+    fn test_capture(recording: Recording) {
+        assert_eq!(recording.len(), 2);
+        assert_eq!(recording.steps()[1].label, "x=2");
+        assert_eq!(recording.steps()[1].grid.row(0), &['#', '#', '#']);
+        assert_eq!(recording.steps()[0].grid.row(0), &['#', '#', '.']);
+        assert_eq!(recording.steps()[0].highlight, vec![Point::new(1, 0)]);
+    }
+
+    #[rstest]
+    fn test_record_outside_capture() {
+        let grid = Grid::new(2, 2, '.');
+
+        let (_, outer) = Recording::capture(|| {
+            record(&grid, "outer");
+            let (_, inner) = Recording::capture(|| record(&grid, "inner"));
+            assert_eq!(inner.len(), 1);
+            assert!(is_recording());
+        });
+
+        record(&grid, "ignored");
+
+        assert!(!is_recording());
+        assert_eq!(outer.len(), 1);
+        assert_eq!(outer.steps()[0].label, "outer");
+    }
+
+    #[rstest]
+    fn test_empty_recording() {
+        assert!(Viewer::new(Recording::default()).is_none());
+    }
+
+    #[rstest]
+    fn test_navigation(recording: Recording) {
+        let mut viewer = Viewer::new(recording).unwrap();
+
+        viewer.handle(KeyCode::Left);
+        assert_eq!(viewer.step(), 0);
+        viewer.handle(KeyCode::Right);
+        viewer.handle(KeyCode::Right);
+        assert_eq!(viewer.step(), 1);
+        viewer.handle(KeyCode::Home);
+        assert_eq!(viewer.step(), 0);
+
+        for key in [KeyCode::Char('2'), KeyCode::Enter] {
+            viewer.handle(key);
+        }
+        assert_eq!(viewer.step(), 1);
+
+        for key in ['l', 'l', 'l', 'j', 'j'] {
+            viewer.handle(KeyCode::Char(key));
+        }
+        assert_eq!(viewer.cursor(), Point::new(2, 1));
+
+        assert!(!viewer.handle(KeyCode::Char('q')));
+    }
+
+    #[rstest]
+    fn test_inspect(recording: Recording) {
+        let mut viewer = Viewer::new(recording).unwrap();
+        viewer.handle(KeyCode::Char('l'));
+
+        assert_eq!(
+            viewer.inspect(),
+            vec![
+                "Cell (1, 0)",
+                "Value '#'",
+                "Highlighted",
+                "Neighbors",
+                "       ",
+                "  # # .",
+                "  . . .",
+            ]
+        );
+    }
+}
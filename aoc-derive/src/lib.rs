@@ -0,0 +1,244 @@
+//! Derive macros for the puzzle solutions, re-exported by `aoc-common`.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, Fields, LitStr, parse_macro_input, parse_quote};
+
+/// Derives `aoc_common::parse::FromLine` and `FromStr` for a struct with named fields, from a
+/// template of the line given as `#[aoc(format = "...")]`.
+///
+/// Fields are written `{name}` in the template, and `{{` and `}}` stand for braces. The text of a
+/// field goes up to the text following it in the template, or to the end of the line for the last
+/// field, and is parsed with the `FromStr` implementation of its type.
+///
+/// ```ignore
+/// #[derive(FromLine)]
+/// #[aoc(format = "{start}-{end}")]
+/// struct Range {
+///     start: u64,
+///     end: u64,
+/// }
+/// ```
+#[proc_macro_derive(FromLine, attributes(aoc))]
+pub fn derive_from_line(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+/// Split a template into the literal text and the fields between it.
+fn parse_template(template: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unterminated field {{{}", name)),
+                    }
+                }
+                if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    return Err(format!("invalid field {{{}}}", name));
+                }
+
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                } else if let Some(Segment::Field(previous)) = segments.last() {
+                    return Err(format!(
+                        "fields {{{}}} and {{{}}} must be separated by some text",
+                        previous, name
+                    ));
+                }
+                segments.push(Segment::Field(name));
+            }
+            '}' => return Err("unmatched } in template, write }} for a brace".to_string()),
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+/// The template of the `#[aoc(format = "...")]` attribute.
+fn template(input: &DeriveInput) -> syn::Result<LitStr> {
+    let mut format = None;
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("aoc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("format") {
+                format = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown aoc attribute, expected `format`"))
+            }
+        })?;
+    }
+
+    format.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "missing #[aoc(format = \"...\")] attribute",
+        )
+    })
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(f) => &f.named,
+            _ => return Err(Error::new_spanned(name, "FromLine needs named fields")),
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                name,
+                "FromLine can only be derived for structs",
+            ));
+        }
+    };
+
+    let format = template(input)?;
+    let segments = parse_template(&format.value()).map_err(|e| Error::new_spanned(&format, e))?;
+
+    let mut statements = Vec::new();
+    let mut values = Vec::new();
+
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(text) => {
+                statements.push(quote! { cursor.expect(#text)?; });
+            }
+            Segment::Field(field) => {
+                if !fields.iter().any(|f| f.ident.as_ref().unwrap() == field) {
+                    return Err(Error::new_spanned(
+                        &format,
+                        format!("no field {} in {}", field, name),
+                    ));
+                }
+                if values.iter().any(|(f, _)| f == field) {
+                    return Err(Error::new_spanned(
+                        &format,
+                        format!("field {} appears twice", field),
+                    ));
+                }
+
+                let until = match segments.get(i + 1) {
+                    Some(Segment::Literal(text)) => quote! { Some(#text) },
+                    _ => quote! { None },
+                };
+                let value = format_ident!("value_{}", i);
+                statements.push(
+                    quote! { let #value = ::aoc_common::parse::field(cursor, #field, #until)?; },
+                );
+                values.push((field.clone(), value));
+            }
+        }
+    }
+
+    let mut assignments = Vec::new();
+    for f in fields {
+        let ident = f.ident.as_ref().unwrap();
+        let Some((_, value)) = values.iter().find(|(n, _)| ident == n) else {
+            return Err(Error::new_spanned(
+                ident,
+                format!("field {} is missing from the template", ident),
+            ));
+        };
+        assignments.push(quote! { #ident: #value });
+    }
+
+    // Every field is parsed with FromStr, with an error that can be displayed
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for f in fields {
+        let ty = &f.ty;
+        where_clause.predicates.push(parse_quote! {
+            #ty: ::std::str::FromStr
+        });
+        where_clause.predicates.push(parse_quote! {
+            <#ty as ::std::str::FromStr>::Err: ::std::fmt::Display
+        });
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::aoc_common::parse::FromLine for #name #ty_generics #where_clause {
+            fn parse_line(
+                cursor: &mut ::aoc_common::parse::Cursor<'_>,
+            ) -> ::std::result::Result<Self, ::aoc_common::parse::ParseError> {
+                #(#statements)*
+
+                Ok(Self { #(#assignments),* })
+            }
+        }
+
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::aoc_common::parse::ParseError;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                ::aoc_common::parse::line(s, ::aoc_common::parse::item::<Self>())
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn field(name: &str) -> Segment {
+        Segment::Field(name.to_string())
+    }
+
+    fn literal(text: &str) -> Segment {
+        Segment::Literal(text.to_string())
+    }
+
+    #[rstest]
+    #[case("{x},{y}", vec![field("x"), literal(","), field("y")])]
+    #[case("move {n} from {a}", vec![literal("move "), field("n"), literal(" from "), field("a")])]
+    #[case("{{{v}}}", vec![literal("{"), field("v"), literal("}")])]
+    fn test_parse_template(#[case] template: &str, #[case] expected: Vec<Segment>) {
+        assert_eq!(parse_template(template), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("{x}{y}", "fields {x} and {y} must be separated by some text")]
+    #[case("{}", "invalid field {}")]
+    #[case("{x-y}", "invalid field {x-y}")]
+    #[case("{x}}", "unmatched } in template, write }} for a brace")]
+    #[case("{x},{y", "unterminated field {y")]
+    fn test_parse_invalid_template(#[case] template: &str, #[case] expected: &str) {
+        assert_eq!(parse_template(template), Err(expected.to_string()));
+    }
+}
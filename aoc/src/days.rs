@@ -0,0 +1,72 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// An inclusive range of days selected on the command line, either a single day (`7`) or a range
+/// of days (`3-7`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayRange {
+    start: u8,
+    end: u8,
+}
+
+impl DayRange {
+    pub fn days(&self) -> RangeInclusive<u8> {
+        self.start..=self.end
+    }
+}
+
+impl FromStr for DayRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = match s.split_once('-') {
+            Some((s, e)) => (parse_day(s)?, parse_day(e)?),
+            None => {
+                let d = parse_day(s)?;
+                (d, d)
+            }
+        };
+
+        if start > end {
+            return Err(format!("Invalid range: {}", s));
+        }
+
+        Ok(DayRange { start, end })
+    }
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    match s.trim().parse::<u8>() {
+        Ok(d) if (1..=25).contains(&d) => Ok(d),
+        _ => Err(format!("Invalid day: {}", s)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("7", 7..=7)]
+    #[case("07", 7..=7)]
+    #[case("3-7", 3..=7)]
+    #[case("12-12", 12..=12)]
+    fn test_parse_day_range(#[case] input: &str, #[case] expected: RangeInclusive<u8>) {
+        let range: DayRange = input.parse().unwrap();
+
+        assert_eq!(range.days(), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("0")]
+    #[case("26")]
+    #[case("foo")]
+    #[case("7-3")]
+    #[case("3-")]
+    fn test_parse_day_range_invalid(#[case] input: &str) {
+        assert!(input.parse::<DayRange>().is_err());
+    }
+}
@@ -0,0 +1,613 @@
+use std::collections::BTreeSet;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::{env, fs};
+
+use aoc_common::answers::{Ledger, Verdict};
+use aoc_common::bench::{
+    BenchOptions,
+    BenchReport,
+    History,
+    HistoryEntry,
+    compare,
+    current_commit,
+};
+use aoc_common::fetch::{Fetcher, Saved};
+use aoc_common::output::Format;
+use aoc_common::viewer::Viewer;
+use aoc_common::{
+    Error,
+    InputSource,
+    Runnable,
+    format_duration,
+    input_dir,
+    tracing_init,
+    tracing_init_with_level,
+};
+use clap::{Args, Parser, Subcommand};
+use tracing::level_filters::LevelFilter;
+
+use crate::days::DayRange;
+
+mod days;
+
+static SOLUTIONS: &[&dyn Runnable] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+];
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2025 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// The days to run, and where to read their input from.
+#[derive(Args)]
+struct Selection {
+    /// Days to run, either a single day (`7`) or an inclusive range (`3-7`)
+    #[arg(required_unless_present = "all")]
+    days: Vec<DayRange>,
+
+    /// Run every available day
+    #[arg(long, conflicts_with = "days")]
+    all: bool,
+
+    /// Read the input from this file instead of the input directory, or from stdin with `-`.
+    /// Only valid when running a single day
+    #[arg(short, long, value_name = "PATH", conflicts_with = "all")]
+    input: Option<InputSource>,
+}
+
+/// Where bench results are recorded, and what they are compared against.
+#[derive(Args)]
+struct HistoryOptions {
+    /// Record the results in the bench history, under the current git commit
+    #[arg(long)]
+    save: bool,
+
+    /// Compare the results against the latest ones of another commit in the bench history, and
+    /// fail if a stage got slower than the threshold
+    #[arg(long)]
+    compare: bool,
+
+    /// Commit to compare against instead of the latest one, implies `--compare`
+    #[arg(long, value_name = "COMMIT")]
+    baseline: Option<String>,
+
+    /// Maximum slowdown of the median duration of a stage, in percent
+    #[arg(long, value_name = "PERCENT", default_value_t = 10.0)]
+    threshold: f64,
+
+    /// Bench history to use instead of `bench-history.jsonl` at the root of the workspace
+    #[arg(long, value_name = "PATH")]
+    history: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solutions of the selected days
+    Run {
+        #[command(flatten)]
+        selection: Selection,
+
+        /// How the results are printed: text, json, tsv or markdown
+        #[arg(short, long, default_value_t = Format::Text)]
+        format: Format,
+    },
+
+    /// Run the solutions of the selected days and check their answers against the recorded ones
+    Verify {
+        #[command(flatten)]
+        selection: Selection,
+
+        /// Record the answers of the parts that don't have one yet
+        #[arg(long)]
+        record: bool,
+
+        /// Answers ledger to use instead of `answers.json` at the root of the workspace
+        #[arg(long, value_name = "PATH")]
+        answers: Option<PathBuf>,
+    },
+
+    /// Benchmark the solutions of the selected days, with repeated runs of each stage
+    Bench {
+        #[command(flatten)]
+        selection: Selection,
+
+        /// Measured runs of each day
+        #[arg(short = 'n', long, default_value = "10")]
+        iterations: NonZeroUsize,
+
+        /// Runs done before measuring
+        #[arg(long, default_value_t = 1)]
+        warmup: usize,
+
+        /// Print the results as JSON
+        #[arg(long)]
+        json: bool,
+
+        #[command(flatten)]
+        history: HistoryOptions,
+    },
+
+    /// Render animations of the solutions of the selected days, for the days that have one
+    Render {
+        #[command(flatten)]
+        selection: Selection,
+
+        /// Directory where the animations are saved, as `dayXX.gif`
+        #[arg(short, long, value_name = "DIR", default_value = "renders")]
+        output: PathBuf,
+
+        /// Also save every frame as a PNG, in a `dayXX` directory next to the animation
+        #[arg(long)]
+        frames: bool,
+    },
+
+    /// Step through the grid simulations of the selected days in the terminal
+    View {
+        #[command(flatten)]
+        selection: Selection,
+    },
+
+    /// Download the inputs of the selected days into the input directory. Requires the session
+    /// cookie of a logged-in user in `SESSION_COOKIE`
+    Fetch {
+        /// Days to download, either a single day (`7`) or an inclusive range (`3-7`)
+        #[arg(required = true)]
+        days: Vec<DayRange>,
+
+        /// Also save the examples of the puzzle descriptions, as `dayXX-example-N.txt`
+        #[arg(long)]
+        examples: bool,
+
+        /// Overwrite the files that already exist, downloading the examples again for those of
+        /// part 2
+        #[arg(long)]
+        refresh: bool,
+
+        /// Download from another server than adventofcode.com
+        #[arg(long, env = "AOC_BASE_URL", value_name = "URL")]
+        base_url: Option<String>,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match cli.command {
+        // Span events would be logged on every iteration and skew the measurements
+        Command::Bench { .. } => tracing_init_with_level(LevelFilter::WARN),
+        // Logs would be written over the viewer
+        Command::View { .. } => tracing_init_with_level(LevelFilter::WARN),
+        // Logs are written to stdout, and would break the output of other formats
+        Command::Run { format, .. } if !format.is_streamed() => {
+            tracing_init_with_level(LevelFilter::WARN)
+        }
+        _ => tracing_init(),
+    }
+
+    match cli.command {
+        Command::Run { selection, format } => run(&selection, format),
+        Command::Verify {
+            selection,
+            record,
+            answers,
+        } => verify(
+            &selection,
+            record,
+            answers.unwrap_or_else(Ledger::default_path),
+        ),
+        Command::Bench {
+            selection,
+            iterations,
+            warmup,
+            json,
+            history,
+        } => {
+            let options = BenchOptions {
+                warmup,
+                iterations: iterations.get(),
+            };
+
+            bench(&selection, &options, json, &history)
+        }
+        Command::Render {
+            selection,
+            output,
+            frames,
+        } => render(&selection, &output, frames),
+        Command::View { selection } => view(&selection),
+        Command::Fetch {
+            days,
+            examples,
+            refresh,
+            base_url,
+        } => fetch(&days, examples, refresh, base_url.as_deref()),
+    }
+}
+
+fn run(selection: &Selection, format: Format) -> ExitCode {
+    let mut reports = Vec::new();
+
+    let res = execute(
+        selection,
+        |s, source| s.run(source),
+        |report| {
+            if format.is_streamed() {
+                print!("{}", format.render(&[report]));
+            } else {
+                reports.push(report);
+            }
+            true
+        },
+    );
+
+    if !format.is_streamed() {
+        print!("{}", format.render(&reports));
+    }
+
+    res
+}
+
+fn verify(selection: &Selection, record: bool, path: PathBuf) -> ExitCode {
+    let mut ledger = match Ledger::load(&path) {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut recorded = false;
+
+    let res = execute(
+        selection,
+        |s, source| s.run(source),
+        |report| {
+            let (v1, v2) = ledger.verify(&report);
+
+            println!("Day {:02}", report.day);
+            println!("Part 1: {} ({})", report.part1, v1);
+            println!("Part 2: {} ({})", report.part2, v2);
+
+            if record {
+                recorded |= ledger.record(&report);
+            }
+
+            !matches!(v1, Verdict::Incorrect { .. }) && !matches!(v2, Verdict::Incorrect { .. })
+        },
+    );
+
+    if recorded {
+        if let Err(e) = ledger.save(&path) {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+        println!("Answers recorded in {}", path.display());
+    }
+
+    res
+}
+
+fn bench(
+    selection: &Selection,
+    options: &BenchOptions,
+    json: bool,
+    history_options: &HistoryOptions,
+) -> ExitCode {
+    let mut reports = Vec::new();
+
+    let res = execute(
+        selection,
+        |s, source| s.bench(source, options),
+        |report| {
+            if !json {
+                println!("Day {:02} ({} iterations)", report.day, report.iterations);
+                print!("{}", report.to_table());
+            }
+            reports.push(report);
+            true
+        },
+    );
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&reports).expect("reports are serializable")
+        );
+    }
+
+    if !history_options.save && !history_options.compare && history_options.baseline.is_none() {
+        return res;
+    }
+
+    let path = history_options
+        .history
+        .clone()
+        .unwrap_or_else(History::default_path);
+    let mut history = match History::load(&path) {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let commit = current_commit().unwrap_or_else(|| "unknown".to_string());
+    let mut regressed = false;
+
+    if history_options.compare || history_options.baseline.is_some() {
+        for report in &reports {
+            let baseline =
+                history.baseline(report.day, &commit, history_options.baseline.as_deref());
+
+            regressed |= !check_regressions(report, baseline, history_options.threshold);
+        }
+    }
+
+    if history_options.save {
+        if let Err(e) = history.append(&commit, &reports) {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+        eprintln!("Results saved in {} for commit {}", path.display(), commit);
+    }
+
+    if regressed { ExitCode::FAILURE } else { res }
+}
+
+fn render(selection: &Selection, output: &Path, frames: bool) -> ExitCode {
+    execute(
+        selection,
+        |s, source| Ok((s.day(), s.render(source)?)),
+        |(day, animation)| {
+            let Some(animation) = animation else {
+                eprintln!("Day {:02}: no animation", day);
+                return true;
+            };
+
+            let path = output.join(format!("day{:02}.gif", day));
+            let mut res = fs::create_dir_all(output)
+                .map_err(|e| Error::Io {
+                    path: output.to_path_buf(),
+                    source: e,
+                })
+                .and_then(|_| animation.save_gif(&path));
+            if frames && res.is_ok() {
+                res = animation.save_frames(&output.join(format!("day{:02}", day)));
+            }
+
+            match res {
+                Ok(()) => {
+                    println!(
+                        "Day {:02}: {} frames saved in {}",
+                        day,
+                        animation.len(),
+                        path.display()
+                    );
+                    true
+                }
+                Err(e) => {
+                    eprintln!("Day {:02}: {}", day, e);
+                    false
+                }
+            }
+        },
+    )
+}
+
+fn view(selection: &Selection) -> ExitCode {
+    execute(
+        selection,
+        |s, source| Ok((s.day(), s.record(source)?)),
+        |(day, recording)| {
+            let Some(mut viewer) = Viewer::new(recording) else {
+                eprintln!("Day {:02}: nothing recorded", day);
+                return true;
+            };
+
+            match viewer.run() {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("Day {:02}: {}", day, e);
+                    false
+                }
+            }
+        },
+    )
+}
+
+/// Print how each stage compares to the baseline, returning whether none of them regressed.
+/// Printed on stderr to keep stdout parseable when the results are output as JSON.
+fn check_regressions(
+    report: &BenchReport,
+    baseline: Option<&HistoryEntry>,
+    threshold: f64,
+) -> bool {
+    let Some(baseline) = baseline else {
+        eprintln!("Day {:02}: no baseline to compare against", report.day);
+        return true;
+    };
+
+    eprintln!(
+        "Day {:02}: median compared to {}",
+        report.day, baseline.commit
+    );
+
+    let mut ok = true;
+
+    for c in compare(&baseline.report, report) {
+        let regression = c.is_regression(threshold);
+        ok &= !regression;
+
+        eprintln!(
+            "{:<8}{:>12} -> {:>12}{:>+9.1}%{}",
+            c.stage,
+            format_duration(c.baseline),
+            format_duration(c.current),
+            c.percent(),
+            if regression { "  REGRESSION" } else { "" }
+        );
+    }
+
+    ok
+}
+
+/// Run the selected days with `run`, handing each result to `on_result`, which returns whether it
+/// is successful.
+fn execute<R>(
+    selection: &Selection,
+    run: impl Fn(&dyn Runnable, &InputSource) -> Result<R, Error>,
+    mut on_result: impl FnMut(R) -> bool,
+) -> ExitCode {
+    let solutions = match select(&selection.days, selection.all) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if selection.input.is_some() && solutions.len() != 1 {
+        eprintln!("An input can only be given when running a single day");
+        return ExitCode::FAILURE;
+    }
+
+    let mut failed = false;
+
+    for s in solutions {
+        let source = selection
+            .input
+            .clone()
+            .unwrap_or_else(|| InputSource::for_day(s.day()));
+
+        match run(s, &source) {
+            Ok(r) => failed |= !on_result(r),
+            Err(e) => {
+                eprintln!("Day {:02}: {}", s.day(), e);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn fetch(days: &[DayRange], examples: bool, refresh: bool, base_url: Option<&str>) -> ExitCode {
+    let Ok(session) = env::var("SESSION_COOKIE") else {
+        eprintln!("Session cookie unavailable, set it in SESSION_COOKIE");
+        return ExitCode::FAILURE;
+    };
+
+    let mut fetcher = Fetcher::new(&session);
+    if let Some(url) = base_url {
+        fetcher = fetcher.with_base_url(url);
+    }
+
+    let days: BTreeSet<u8> = days.iter().flat_map(DayRange::days).collect();
+    let mut failed = false;
+
+    for d in days {
+        if let Err(e) = fetch_day(&fetcher, d, examples, refresh) {
+            eprintln!("Day {:02}: {}", d, e);
+            failed = true;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn fetch_day(fetcher: &Fetcher, day: u8, examples: bool, refresh: bool) -> Result<(), Error> {
+    for saved in fetcher.save(day, &input_dir(), examples, refresh)? {
+        match saved {
+            Saved::Written(path) => println!("Saved {}", path.display()),
+            Saved::Exists(path) => println!("{} already exists", path.display()),
+        }
+    }
+
+    Ok(())
+}
+
+fn select(days: &[DayRange], all: bool) -> Result<Vec<&'static dyn Runnable>, String> {
+    if all {
+        return Ok(SOLUTIONS.to_vec());
+    }
+
+    let days: BTreeSet<u8> = days.iter().flat_map(DayRange::days).collect();
+
+    days.into_iter()
+        .map(|d| {
+            SOLUTIONS
+                .iter()
+                .find(|s| s.day() == d)
+                .copied()
+                .ok_or_else(|| format!("No solution for day {}", d))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    fn test_solutions_are_sorted_and_unique() {
+        let days = SOLUTIONS.iter().map(|s| s.day()).collect::<Vec<u8>>();
+
+        assert_eq!(days, (1..=12).collect::<Vec<u8>>());
+    }
+
+    #[rstest]
+    fn test_select_all() {
+        let selected = select(&[], true).unwrap();
+
+        assert_eq!(selected.len(), SOLUTIONS.len());
+    }
+
+    #[rstest]
+    fn test_select_ranges() {
+        let ranges = [
+            "5-7".parse().unwrap(),
+            "1".parse().unwrap(),
+            "6".parse().unwrap(),
+        ];
+        let selected = select(&ranges, false).unwrap();
+
+        let days = selected.iter().map(|s| s.day()).collect::<Vec<u8>>();
+        assert_eq!(days, vec![1, 5, 6, 7]);
+    }
+
+    #[rstest]
+    fn test_select_unknown_day() {
+        let ranges = ["13".parse().unwrap()];
+
+        assert_eq!(
+            select(&ranges, false).err(),
+            Some("No solution for day 13".to_string())
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt::Display;
+
+use aoc_common::Solution;
+use tracing::debug;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Input = Vec<String>;
+    type Parsed = Vec<Rotation>;
+
+    fn parse(input: &Vec<String>) -> Result<Vec<Rotation>, String> {
+        parse(input)
+    }
+
+    fn part1(rotations: &Vec<Rotation>) -> impl Display {
+        let (p1, _) = get_passwords(rotations);
+
+        p1
+    }
+
+    fn part2(rotations: &Vec<Rotation>) -> impl Display {
+        let (_, p2) = get_passwords(rotations);
+
+        p2
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Rotation {
+    Left(i16),
+    Right(i16),
+}
+
+#[tracing::instrument(skip_all)]
+fn parse(input: &[String]) -> Result<Vec<Rotation>, String> {
+    input
+        .iter()
+        .map(|entry| {
+            let amount = entry
+                .get(1..)
+                .and_then(|a| a.parse().ok())
+                .ok_or_else(|| format!("Invalid rotation: {}", entry))?;
+
+            match entry.chars().next() {
+                Some('L') => Ok(Rotation::Left(amount)),
+                Some('R') => Ok(Rotation::Right(amount)),
+                _ => Err(format!("Invalid direction: {}", entry)),
+            }
+        })
+        .collect()
+}
+
+#[tracing::instrument(skip_all)]
+fn get_passwords(rotations: &[Rotation]) -> (i16, i16) {
+    let mut count_exact = 0;
+    let mut count_pass = 0;
+    let mut pos: i16 = 50;
+
+    for &r in rotations {
+        let amount = match r {
+            Rotation::Left(a) | Rotation::Right(a) => a,
+        };
+
+        let prev = pos;
+
+        if amount >= 100 {
+            count_pass += amount / 100;
+        }
+
+        let rot = amount % 100;
+
+        match r {
+            Rotation::Left(_) => pos -= rot,
+            Rotation::Right(_) => pos += rot,
+        }
+
+        if (prev != 0 && pos < 0) || pos > 100 {
+            count_pass += 1;
+        }
+
+        pos = (pos + 100) % 100;
+        if pos == 0 {
+            count_exact += 1;
+        }
+
+        debug!("The dial is rotated {:?} to point at {}.", r, pos);
+    }
+
+    (count_exact, count_pass + count_exact)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{get_input, parse_test_input};
+    use rstest::{fixture, rstest};
+
+    use super::*;
+
+    #[fixture]
+    fn test_input() -> Vec<String> {
+        parse_test_input(
+            "
+            L68
+            L30
+            R48
+            L5
+            R60
+            L55
+            L1
+            L99
+            R14
+            L82
+        ",
+        )
+    }
+
+    #[fixture]
+    fn puzzle_input() -> Vec<String> {
+        get_input("day01.txt")
+    }
+
+    #[rstest]
+    fn test_p1(test_input: Vec<String>) {
+        let rotations = parse(&test_input).expect("invalid input");
+        let (res, _) = get_passwords(&rotations);
+
+        assert_eq!(res, 3);
+    }
+
+    #[rstest]
+    fn test_p1_full_input(puzzle_input: Vec<String>) {
+        let rotations = parse(&puzzle_input).expect("invalid input");
+        let (res, _) = get_passwords(&rotations);
+
+        assert_eq!(res, 1141);
+    }
+
+    #[rstest]
+    fn test_p2(test_input: Vec<String>) {
+        let rotations = parse(&test_input).expect("invalid input");
+        let (_, res) = get_passwords(&rotations);
+
+        assert_eq!(res, 6);
+    }
+
+    #[rstest]
+    fn test_p2_full_input(puzzle_input: Vec<String>) {
+        let rotations = parse(&puzzle_input).expect("invalid input");
+        let (_, res) = get_passwords(&rotations);
+
+        assert_eq!(res, 6634);
+    }
+}
@@ -1,55 +1,45 @@
 use std::collections::HashMap;
 use std::fmt::Display;
-use std::time::Instant;
 
-use aoc_common::{format_duration, get_input_as_string, tracing_init};
-use itertools::Itertools;
+use aoc_common::Solution;
+use aoc_common::parse::{self, ParseError, range, separated, unsigned};
 
-fn main() {
-    tracing_init();
-
-    let input = get_input_as_string("day02.txt");
-
-    let start = Instant::now();
-
-    let (r1, r2) = solve(input.as_str());
-
-    let t = start.elapsed().as_nanos();
-
-    println!("Part 1: {}", r1);
-    println!("Part 2: {}", r2);
-    println!("Duration: {}", format_duration(t));
-}
-
-struct Range {
+pub struct Range {
     start: u64,
     end: u64,
 }
 
-#[tracing::instrument(skip_all)]
-fn solve(input: &str) -> (impl Display, impl Display) {
-    let ranges = parse_ranges(input);
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = String;
+    type Parsed = Vec<Range>;
+
+    fn parse(input: &String) -> Result<Vec<Range>, String> {
+        Ok(parse_ranges(input)?)
+    }
 
-    let p1 = get_sum_of_invalid_ids(&ranges);
-    let p2 = get_sum_of_real_invalid_ids(&ranges);
+    fn part1(ranges: &Vec<Range>) -> impl Display {
+        get_sum_of_invalid_ids(ranges)
+    }
 
-    (p1, p2)
+    fn part2(ranges: &Vec<Range>) -> impl Display {
+        get_sum_of_real_invalid_ids(ranges)
+    }
 }
 
 #[tracing::instrument(skip_all)]
-fn parse_ranges(input: &str) -> Vec<Range> {
-    input
-        .split(',')
-        .map(|r| {
-            let (s, e) = r
-                .splitn(2, '-')
-                .map(|i| i.parse::<u64>().unwrap())
-                .collect_tuple()
-                .unwrap();
-
-            Range { start: s, end: e }
+fn parse_ranges(input: &str) -> Result<Vec<Range>, ParseError> {
+    let ranges = parse::line(input, separated(range(unsigned::<u64>()), ","))?;
+
+    Ok(ranges
+        .into_iter()
+        .map(|r| Range {
+            start: *r.start(),
+            end: *r.end(),
         })
-        .collect_vec()
+        .collect())
 }
 
 #[tracing::instrument(skip_all)]
@@ -141,7 +131,7 @@ fn is_repeating(n: u64, l: u32, chunk_size: u32) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use aoc_common::parse_test_input_as_string;
+    use aoc_common::{get_input_as_string, parse_test_input_as_string};
     use rstest::{fixture, rstest};
 
     use super::*;
@@ -160,7 +150,7 @@ mod tests {
 
     #[rstest]
     fn test_p1(test_input: String) {
-        let ranges = parse_ranges(&test_input);
+        let ranges = parse_ranges(&test_input).expect("invalid input");
         let res = get_sum_of_invalid_ids(&ranges);
 
         assert_eq!(res, 1227775554);
@@ -168,7 +158,7 @@ mod tests {
 
     #[rstest]
     fn test_p1_full_input(puzzle_input: String) {
-        let ranges = parse_ranges(&puzzle_input);
+        let ranges = parse_ranges(&puzzle_input).expect("invalid input");
         let res = get_sum_of_invalid_ids(&ranges);
 
         assert_eq!(res, 17077011375);
@@ -176,7 +166,7 @@ mod tests {
 
     #[rstest]
     fn test_p2(test_input: String) {
-        let ranges = parse_ranges(&test_input);
+        let ranges = parse_ranges(&test_input).expect("invalid input");
         let res = get_sum_of_real_invalid_ids(&ranges);
 
         assert_eq!(res, 4174379265);
@@ -184,7 +174,7 @@ mod tests {
 
     #[rstest]
     fn test_p2_full_input(puzzle_input: String) {
-        let ranges = parse_ranges(&puzzle_input);
+        let ranges = parse_ranges(&puzzle_input).expect("invalid input");
         let res = get_sum_of_real_invalid_ids(&ranges);
 
         assert_eq!(res, 36037497037);
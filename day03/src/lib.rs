@@ -1,39 +1,41 @@
 use std::fmt::Display;
-use std::time::Instant;
 
-use aoc_common::{format_duration, get_input, tracing_init};
+use aoc_common::Solution;
 
-fn main() {
-    tracing_init();
+pub struct Day03;
 
-    let input = get_input("day03.txt");
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input = Vec<String>;
+    type Parsed = Vec<Vec<u8>>;
 
-    let start = Instant::now();
-
-    let (r1, r2) = solve(input.as_slice());
-
-    let t = start.elapsed().as_nanos();
-
-    println!("Part 1: {}", r1);
-    println!("Part 2: {}", r2);
-    println!("Duration: {}", format_duration(t));
-}
-
-#[tracing::instrument(skip_all)]
-fn solve(input: &[String]) -> (impl Display, impl Display) {
-    let banks = parse_banks(input);
+    fn parse(input: &Vec<String>) -> Result<Vec<Vec<u8>>, String> {
+        parse_banks(input)
+    }
 
-    let p1 = get_total_joltage(&banks, 2);
-    let p2 = get_total_joltage(&banks, 12);
+    fn part1(banks: &Vec<Vec<u8>>) -> impl Display {
+        get_total_joltage(banks, 2)
+    }
 
-    (p1, p2)
+    fn part2(banks: &Vec<Vec<u8>>) -> impl Display {
+        get_total_joltage(banks, 12)
+    }
 }
 
 #[tracing::instrument(skip_all)]
-fn parse_banks(input: &[String]) -> Vec<Vec<u8>> {
+fn parse_banks(input: &[String]) -> Result<Vec<Vec<u8>>, String> {
     input
         .iter()
-        .map(|b| b.chars().map(|c| c.to_digit(10).unwrap() as u8).collect())
+        .enumerate()
+        .map(|(i, b)| {
+            b.chars()
+                .map(|c| {
+                    c.to_digit(10)
+                        .map(|d| d as u8)
+                        .ok_or_else(|| format!("Invalid battery {:?} on line {}: {}", c, i + 1, b))
+                })
+                .collect()
+        })
         .collect()
 }
 
@@ -77,7 +79,7 @@ fn get_highest_joltage(bank: &[u8], n: u32) -> u64 {
 
 #[cfg(test)]
 mod tests {
-    use aoc_common::parse_test_input;
+    use aoc_common::{get_input, parse_test_input};
     use rstest::{fixture, rstest};
 
     use super::*;
@@ -99,9 +101,16 @@ mod tests {
         get_input("day03.txt")
     }
 
+    #[rstest]
+    fn test_parse_invalid_battery() {
+        let res = parse_banks(&parse_test_input("12\nx?"));
+
+        assert_eq!(res, Err("Invalid battery 'x' on line 2: x?".to_string()));
+    }
+
     #[rstest]
     fn test_p1(test_input: Vec<String>) {
-        let banks = parse_banks(&test_input);
+        let banks = parse_banks(&test_input).expect("invalid input");
         let res = get_total_joltage(&banks, 2);
 
         assert_eq!(res, 357);
@@ -109,7 +118,7 @@ mod tests {
 
     #[rstest]
     fn test_p1_full_input(puzzle_input: Vec<String>) {
-        let banks = parse_banks(&puzzle_input);
+        let banks = parse_banks(&puzzle_input).expect("invalid input");
         let res = get_total_joltage(&banks, 2);
 
         assert_eq!(res, 17766);
@@ -117,7 +126,7 @@ mod tests {
 
     #[rstest]
     fn test_p2(test_input: Vec<String>) {
-        let banks = parse_banks(&test_input);
+        let banks = parse_banks(&test_input).expect("invalid input");
         let res = get_total_joltage(&banks, 12);
 
         assert_eq!(res, 3121910778619);
@@ -125,7 +134,7 @@ mod tests {
 
     #[rstest]
     fn test_p2_full_input(puzzle_input: Vec<String>) {
-        let banks = parse_banks(&puzzle_input);
+        let banks = parse_banks(&puzzle_input).expect("invalid input");
         let res = get_total_joltage(&banks, 12);
 
         assert_eq!(res, 176582889354075);
@@ -0,0 +1,189 @@
+use std::fmt::{Debug, Display, Formatter};
+
+use aoc_common::grid::{Automaton, Grid, Update};
+use aoc_common::render::{Animation, Renderer, Rgb};
+use aoc_common::{Point, Solution};
+use tracing::debug;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Element {
+    Empty,
+    RollOfPaper,
+}
+
+impl Display for Element {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&format!("{:?}", self), f)
+    }
+}
+
+impl TryFrom<char> for Element {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Element::Empty),
+            '@' => Ok(Element::RollOfPaper),
+            _ => Err(format!("Invalid value: {}", value)),
+        }
+    }
+}
+
+impl From<Element> for char {
+    fn from(value: Element) -> Self {
+        match value {
+            Element::Empty => '.',
+            Element::RollOfPaper => '@',
+        }
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = Vec<String>;
+    type Parsed = Grid<Element>;
+
+    fn parse(input: &Vec<String>) -> Result<Grid<Element>, String> {
+        Grid::from_input(input)
+    }
+
+    fn part1(grid: &Grid<Element>) -> impl Display {
+        get_accessible_rolls(grid)
+    }
+
+    fn part2(grid: &Grid<Element>) -> impl Display {
+        get_accessible_rolls_recursive(grid)
+    }
+
+    fn animate(grid: &Grid<Element>) -> Option<Animation> {
+        Some(animate_removals(grid))
+    }
+}
+
+/// Rolls of paper with fewer than 4 rolls around them can be accessed by a forklift, and removed.
+fn remove_accessible_roll(grid: &Grid<Element>, p: &Point<usize>) -> Element {
+    if grid[*p] != Element::RollOfPaper {
+        return grid[*p];
+    }
+
+    let n_adjacent_rolls = grid.count_neighbors(p, |&v| v == Element::RollOfPaper);
+
+    debug!("{:?}: Has {} adjacent rolls", p, n_adjacent_rolls);
+
+    if n_adjacent_rolls < 4 {
+        Element::Empty
+    } else {
+        Element::RollOfPaper
+    }
+}
+
+#[tracing::instrument(skip_all)]
+fn get_accessible_rolls(grid: &Grid<Element>) -> usize {
+    Automaton::new(grid.clone(), remove_accessible_roll).step()
+}
+
+#[tracing::instrument(skip_all)]
+fn get_accessible_rolls_recursive(grid: &Grid<Element>) -> usize {
+    let mut automaton =
+        Automaton::new(grid.clone(), remove_accessible_roll).with_update(Update::Frontier);
+
+    let passes = automaton.run_to_fixpoint();
+    debug!("All accessible rolls removed after {} passes", passes);
+
+    automaton.changes()
+}
+
+/// One frame per pass of removals, with the rolls about to be removed in red.
+fn animate_removals(grid: &Grid<Element>) -> Animation {
+    let mut renderer = Renderer::new(|e: &Element| match e {
+        Element::Empty => Rgb::BLACK,
+        Element::RollOfPaper => Rgb::WHITE,
+    })
+    .with_scale(4)
+    .with_highlight_color(Rgb::RED);
+    let mut animation = Animation::new();
+
+    // Synchronous updates, so each pass only removes the rolls accessible at its start
+    let mut automaton = Automaton::new(grid.clone(), remove_accessible_roll);
+
+    loop {
+        let before = automaton.grid().clone();
+        if automaton.step() == 0 {
+            break;
+        }
+
+        let after = automaton.grid();
+        renderer.set_highlight(before.positions().filter(|&p| before[p] != after[p]));
+        animation.push(renderer.image(&before));
+    }
+
+    renderer.set_highlight([]);
+    animation.push(renderer.image(automaton.grid()));
+
+    animation
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{get_input, parse_test_input};
+    use rstest::{fixture, rstest};
+
+    use super::*;
+
+    #[fixture]
+    fn test_input() -> Vec<String> {
+        parse_test_input(
+            "
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@.
+        ",
+        )
+    }
+
+    #[fixture]
+    fn puzzle_input() -> Vec<String> {
+        get_input("day04.txt")
+    }
+
+    #[rstest]
+    fn test_p1(test_input: Vec<String>) {
+        let grid: Grid<Element> = Grid::from_input(&test_input).unwrap();
+        let res = get_accessible_rolls(&grid);
+
+        assert_eq!(res, 13);
+    }
+
+    #[rstest]
+    fn test_p1_full_input(puzzle_input: Vec<String>) {
+        let grid: Grid<Element> = Grid::from_input(&puzzle_input).unwrap();
+        let res = get_accessible_rolls(&grid);
+
+        assert_eq!(res, 1480);
+    }
+
+    #[rstest]
+    fn test_p2(test_input: Vec<String>) {
+        let grid: Grid<Element> = Grid::from_input(&test_input).unwrap();
+        let res = get_accessible_rolls_recursive(&grid);
+
+        assert_eq!(res, 43);
+    }
+
+    #[rstest]
+    fn test_p2_full_input(puzzle_input: Vec<String>) {
+        let grid: Grid<Element> = Grid::from_input(&puzzle_input).unwrap();
+        let res = get_accessible_rolls_recursive(&grid);
+
+        assert_eq!(res, 8899);
+    }
+}
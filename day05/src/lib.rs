@@ -1,36 +1,31 @@
 use std::fmt::Display;
-use std::time::Instant;
 
-use aoc_common::{format_duration, get_input, tracing_init};
+use aoc_common::Solution;
+use aoc_common::parse::{self, FromLine, ParseError, item, unsigned};
 use itertools::Itertools;
 
-fn main() {
-    tracing_init();
+pub struct Day05;
 
-    let input = get_input("day05.txt");
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = Vec<String>;
+    type Parsed = DB;
 
-    let start = Instant::now();
-
-    let (r1, r2) = solve(input.as_slice());
-
-    let t = start.elapsed().as_nanos();
-
-    println!("Part 1: {}", r1);
-    println!("Part 2: {}", r2);
-    println!("Duration: {}", format_duration(t));
-}
-
-#[tracing::instrument(skip_all)]
-fn solve(input: &[String]) -> (impl Display, impl Display) {
-    let db = parse(input);
+    fn parse(input: &Vec<String>) -> Result<DB, String> {
+        Ok(parse(input)?)
+    }
 
-    let p1 = count_fresh_ingredients(&db);
-    let p2 = count_possible_valid_ingredients(&db);
+    fn part1(db: &DB) -> impl Display {
+        count_fresh_ingredients(db)
+    }
 
-    (p1, p2)
+    fn part2(db: &DB) -> impl Display {
+        count_possible_valid_ingredients(db)
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, FromLine)]
+#[aoc(format = "{s}-{e}")]
 struct Range {
     s: u64,
     e: u64,
@@ -57,40 +52,20 @@ impl Range {
 }
 
 #[derive(Debug)]
-struct DB {
+pub struct DB {
     ranges: Vec<Range>,
     values: Vec<u64>,
 }
 
 #[tracing::instrument(skip_all)]
-fn parse(input: &[String]) -> DB {
-    let mut db = DB {
-        ranges: Vec::new(),
-        values: Vec::new(),
-    };
-
-    for i in input {
-        if i.is_empty() {
-            continue;
-        }
-
-        let values: Vec<&str> = i.splitn(2, '-').collect();
-
-        match values.len() {
-            1 => {
-                let v = values[0].parse().expect("invalid value");
-                db.values.push(v);
-            }
-            2 => {
-                let s = values[0].parse().expect("invalid value");
-                let e = values[1].parse().expect("invalid value");
-                db.ranges.push(Range { s, e })
-            }
-            _ => panic!("Invalid number of values in line: {}", i),
-        }
-    }
-
-    db
+fn parse(input: &[String]) -> Result<DB, ParseError> {
+    // The fresh ranges, then the available ingredients
+    let [ranges, values] = parse::split_sections(input)?;
+
+    Ok(DB {
+        ranges: ranges.parse(item())?,
+        values: values.parse(unsigned())?,
+    })
 }
 
 fn count_fresh_ingredients(db: &DB) -> usize {
@@ -143,7 +118,7 @@ fn remove_overlaps(ranges: &[Range]) -> Vec<Range> {
 
 #[cfg(test)]
 mod tests {
-    use aoc_common::parse_test_input;
+    use aoc_common::{get_input, parse_test_input};
     use rstest::{fixture, rstest};
 
     use super::*;
@@ -172,9 +147,32 @@ mod tests {
         get_input("day05.txt")
     }
 
+    #[rstest]
+    #[case(
+        "
+        3-5
+
+        1
+        6-8
+        ",
+        "Line 4, column 2: unexpected '-'"
+    )]
+    #[case(
+        "
+        3-5
+        1
+        ",
+        "Line 2, column 2: expected 2 sections, found 1"
+    )]
+    fn test_parse_invalid(#[case] input: &str, #[case] expected: &str) {
+        let err = parse(&parse_test_input(input)).unwrap_err();
+
+        assert_eq!(err.to_string(), expected);
+    }
+
     #[rstest]
     fn test_p1(test_input: Vec<String>) {
-        let db = parse(&test_input);
+        let db = parse(&test_input).expect("invalid input");
         let res = count_fresh_ingredients(&db);
 
         assert_eq!(res, 3);
@@ -182,7 +180,7 @@ mod tests {
 
     #[rstest]
     fn test_p1_full_input(puzzle_input: Vec<String>) {
-        let db = parse(&puzzle_input);
+        let db = parse(&puzzle_input).expect("invalid input");
         let res = count_fresh_ingredients(&db);
 
         assert_eq!(res, 513);
@@ -190,7 +188,7 @@ mod tests {
 
     #[rstest]
     fn test_p2(test_input: Vec<String>) {
-        let db = parse(&test_input);
+        let db = parse(&test_input).expect("invalid input");
         let res = count_possible_valid_ingredients(&db);
 
         assert_eq!(res, 14);
@@ -198,7 +196,7 @@ mod tests {
 
     #[rstest]
     fn test_p2_full_input(puzzle_input: Vec<String>) {
-        let db = parse(&puzzle_input);
+        let db = parse(&puzzle_input).expect("invalid input");
         let res = count_possible_valid_ingredients(&db);
 
         assert_eq!(res, 339668510830757);
@@ -1,32 +1,41 @@
 use std::fmt::Display;
-use std::time::Instant;
 
-use aoc_common::{format_duration, get_input, tracing_init};
+use aoc_common::Solution;
+use aoc_common::grid::GridParser;
+use aoc_common::parse::{self, fields, unsigned};
 
-fn main() {
-    tracing_init();
+pub struct Day06;
 
-    let input = get_input("day06.txt");
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = Vec<String>;
+    type Parsed = Worksheet;
 
-    let start = Instant::now();
+    fn parse(input: &Vec<String>) -> Result<Worksheet, String> {
+        // Both readings need at least the line of operators
+        if input.is_empty() {
+            return Err("Empty input".to_string());
+        }
 
-    let (r1, r2) = solve(input.as_slice());
+        Ok(Worksheet {
+            problems: parse_problems(input)?,
+            cephalopod_problems: parse_problems_like_a_dumbass(input)?,
+        })
+    }
 
-    let t = start.elapsed().as_nanos();
+    fn part1(worksheet: &Worksheet) -> impl Display {
+        get_sum_of_results(&worksheet.problems)
+    }
 
-    println!("Part 1: {}", r1);
-    println!("Part 2: {}", r2);
-    println!("Duration: {}", format_duration(t));
+    fn part2(worksheet: &Worksheet) -> impl Display {
+        get_sum_of_results(&worksheet.cephalopod_problems)
+    }
 }
 
-#[tracing::instrument(skip_all)]
-fn solve(input: &[String]) -> (impl Display, impl Display) {
-    let problems = parse_problems(input).expect("Failed to parse problems");
-    let p1 = get_sum_of_results(&problems);
-    let problems = parse_problems_like_a_dumbass(input).expect("Failed to parse problems");
-    let p2 = get_sum_of_results(&problems);
-
-    (p1, p2)
+/// The problems of the worksheet, read both the human way and the cephalopod way.
+pub struct Worksheet {
+    problems: Vec<Problem>,
+    cephalopod_problems: Vec<Problem>,
 }
 
 #[derive(Debug, Clone)]
@@ -63,11 +72,8 @@ impl Problem {
 
 #[tracing::instrument(skip_all)]
 fn parse_problems(input: &[String]) -> Result<Vec<Problem>, String> {
-    let len = input.len();
-    let ops = input
-        .iter()
-        .nth_back(0)
-        .unwrap()
+    let (ops, lines) = input.split_last().ok_or("Empty input")?;
+    let ops = ops
         .split(' ')
         .filter(|s| !s.is_empty())
         .collect::<Vec<&str>>();
@@ -78,23 +84,14 @@ fn parse_problems(input: &[String]) -> Result<Vec<Problem>, String> {
         .map(|&s| {
             let op = Op::try_from(s)?;
             Ok(Problem {
-                values: Vec::with_capacity(len - 1),
+                values: Vec::with_capacity(lines.len()),
                 op,
             })
         })
         .collect::<Result<Vec<Problem>, String>>()?;
 
-    for entry in input.iter().take(len - 1) {
-        let values = entry
-            .split(' ')
-            .filter_map(|s| {
-                if s.is_empty() {
-                    return None;
-                }
-
-                s.parse::<u64>().ok()
-            })
-            .collect::<Vec<u64>>();
+    for (i, entry) in lines.iter().enumerate() {
+        let values = parse::line_at(i + 1, entry, fields(unsigned::<u64>()))?;
 
         if values.len() != n {
             return Err(format!("Invalid number of values: {}", values.len()));
@@ -110,21 +107,11 @@ fn parse_problems(input: &[String]) -> Result<Vec<Problem>, String> {
 
 #[tracing::instrument(skip_all)]
 fn parse_problems_like_a_dumbass(input: &[String]) -> Result<Vec<Problem>, String> {
-    let h = input.len();
-    let w = input.iter().map(|r| r.len()).max().unwrap();
-
-    let mut cols = (0..w)
-        .map(|_| Vec::with_capacity(h))
-        .collect::<Vec<Vec<char>>>();
-
-    for r in input {
-        r.chars()
-            .enumerate()
-            .filter(|(_, c)| *c != ' ')
-            .for_each(|(i, c)| {
-                cols[i].push(c);
-            })
-    }
+    // Lines are not padded to the same length, the missing cells are blank
+    let grid = GridParser::new(Some).with_padding(' ').parse(input)?;
+    let (w, h) = (grid.width, grid.height);
+
+    let cols = grid.transpose();
 
     let mut problems = Vec::with_capacity(w);
     let mut problem = Problem {
@@ -132,8 +119,10 @@ fn parse_problems_like_a_dumbass(input: &[String]) -> Result<Vec<Problem>, Strin
         op: Op::Add,
     };
 
-    for col in cols {
-        if col.is_empty() {
+    for x in 0..cols.height {
+        let col = cols.row(x);
+
+        if col.iter().all(|&c| c == ' ') {
             problems.push(problem.clone());
             problem = Problem {
                 values: Vec::with_capacity(h - 1),
@@ -144,14 +133,14 @@ fn parse_problems_like_a_dumbass(input: &[String]) -> Result<Vec<Problem>, Strin
 
         let mut val: u64 = 0;
 
-        for c in &col {
+        for c in col {
             if c.is_ascii_digit() {
                 val = val * 10 + c.to_digit(10).unwrap() as u64;
             }
         }
         problem.values.push(val);
 
-        if *col.iter().last().unwrap() == '*' {
+        if col[h - 1] == '*' {
             problem.op = Op::Mul;
         }
     }
@@ -168,7 +157,7 @@ fn get_sum_of_results(problems: &[Problem]) -> u64 {
 
 #[cfg(test)]
 mod tests {
-    use aoc_common::parse_test_input;
+    use aoc_common::{get_input, parse_test_input};
     use rstest::{fixture, rstest};
 
     use super::*;
@@ -190,6 +179,12 @@ mod tests {
         get_input("day06.txt")
     }
 
+    #[rstest]
+    fn test_parse_empty_input() {
+        assert!(matches!(Day06::parse(&vec![]), Err(e) if e == "Empty input"));
+        assert!(parse_problems(&[]).is_err());
+    }
+
     #[rstest]
     fn test_p1(test_input: Vec<String>) {
         let problems = parse_problems(&test_input).expect("Failed to parse test input");
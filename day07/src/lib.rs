@@ -0,0 +1,249 @@
+use std::fmt::Display;
+
+use aoc_common::grid::{Grid, GridParser};
+use aoc_common::render::{Animation, Renderer, Rgb};
+use aoc_common::search::count_paths;
+use aoc_common::{Direction, Point, Solution, viewer};
+use tracing::debug;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Input = Vec<String>;
+    type Parsed = Manifold;
+
+    fn parse(input: &Vec<String>) -> Result<Manifold, String> {
+        let (grid, markers) = GridParser::new(|c| Element::try_from(c).ok())
+            .with_marker('S', Element::Empty)
+            .parse_with_markers(input)?;
+
+        Ok(Manifold {
+            grid,
+            start: markers.single('S')?,
+        })
+    }
+
+    fn part1(manifold: &Manifold) -> impl Display {
+        get_number_of_splits(manifold)
+    }
+
+    fn part2(manifold: &Manifold) -> impl Display {
+        get_timelines(manifold)
+    }
+
+    fn animate(manifold: &Manifold) -> Option<Animation> {
+        Some(animate_beams(manifold))
+    }
+}
+
+#[derive(Clone)]
+pub struct Manifold {
+    grid: Grid<Element>,
+    start: Point<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Element {
+    Splitter,
+    Beam,
+    Empty,
+}
+
+impl TryFrom<char> for Element {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' => Ok(Element::Splitter),
+            '|' => Ok(Element::Beam),
+            '.' => Ok(Element::Empty),
+            e => Err(format!("invalid element {:?}", e)),
+        }
+    }
+}
+
+impl From<Element> for char {
+    fn from(value: Element) -> Self {
+        match value {
+            Element::Splitter => '^',
+            Element::Beam => '|',
+            Element::Empty => '.',
+        }
+    }
+}
+
+#[tracing::instrument(skip_all)]
+fn get_number_of_splits(manifold: &Manifold) -> usize {
+    let mut grid = manifold.grid.clone();
+
+    // The beam enters the manifold at the start
+    grid[manifold.start] = Element::Beam;
+
+    let mut splits = 0;
+    for y in 0..grid.height {
+        let splitters = propagate_row(&mut grid, y);
+        splits += splitters.len();
+
+        viewer::record_with_highlight(&grid, format_args!("row {}", y), splitters);
+    }
+
+    splits
+}
+
+/// Extend the beams of the row above into row `y`, returning the splitters they hit.
+fn propagate_row(grid: &mut Grid<Element>, y: usize) -> Vec<Point<usize>> {
+    let mut splitters = Vec::new();
+
+    for x in 0..grid.width {
+        let pos = Point::new(x, y);
+        let cur = grid[pos];
+
+        debug!(
+            "pos={:?}, cur={:?}, above={:?}",
+            pos,
+            cur,
+            grid.get_above(&pos)
+        );
+
+        match cur {
+            Element::Empty => {
+                if let Some(Element::Beam) = grid.get_above(&pos) {
+                    debug!("above is beam, setting {:?} to beam", pos);
+                    grid.set(&pos, Element::Beam);
+                }
+            }
+            Element::Splitter => {
+                if let Some(Element::Beam) = grid.get_above(&pos) {
+                    splitters.push(pos);
+                    for d in [Direction::West, Direction::East] {
+                        if let Some(p) = pos.checked_step(d).filter(|p| p.x < grid.width) {
+                            grid.set(&p, Element::Beam);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    splitters
+}
+
+/// One frame per row the beams go through, with the splitters they hit highlighted.
+fn animate_beams(manifold: &Manifold) -> Animation {
+    let mut renderer = Renderer::new(|e: &Element| match e {
+        Element::Splitter => Rgb::GREY,
+        Element::Beam => Rgb::BLUE,
+        Element::Empty => Rgb::BLACK,
+    })
+    .with_scale(4);
+    let mut animation = Animation::new().with_delay(50);
+
+    let mut grid = manifold.grid.clone();
+    grid[manifold.start] = Element::Beam;
+
+    for y in 0..grid.height {
+        let splitters = propagate_row(&mut grid, y);
+        renderer.set_highlight(splitters);
+        animation.push(renderer.image(&grid));
+    }
+
+    animation
+}
+
+#[tracing::instrument(skip_all)]
+fn get_timelines(manifold: &Manifold) -> usize {
+    let grid = &manifold.grid;
+
+    // A timeline ends when its beam leaves the manifold, past the last row
+    count_paths(
+        manifold.start,
+        |&pos| get_children(grid, pos),
+        |pos| pos.y >= grid.height,
+    )
+}
+
+/// The cells a beam goes to after a cell, on the row below.
+fn get_children(grid: &Grid<Element>, pos: Point<usize>) -> Vec<Point<usize>> {
+    let below = Point::new(pos.x, pos.y + 1);
+
+    match grid[pos] {
+        Element::Empty => vec![below],
+        Element::Splitter => [Direction::West, Direction::East]
+            .into_iter()
+            .filter_map(|d| below.checked_step(d))
+            .filter(|p| p.x < grid.width)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{get_input, parse_test_input};
+    use rstest::{fixture, rstest};
+
+    use super::*;
+
+    #[fixture]
+    fn test_input() -> Vec<String> {
+        parse_test_input(
+            "
+            .......S.......
+            ...............
+            .......^.......
+            ...............
+            ......^.^......
+            ...............
+            .....^.^.^.....
+            ...............
+            ....^.^...^....
+            ...............
+            ...^.^...^.^...
+            ...............
+            ..^...^.....^..
+            ...............
+            .^.^.^.^.^...^.
+            ...............
+        ",
+        )
+    }
+
+    #[fixture]
+    fn puzzle_input() -> Vec<String> {
+        get_input("day07.txt")
+    }
+
+    #[rstest]
+    fn test_p1(test_input: Vec<String>) {
+        let manifold = Day07::parse(&test_input).expect("unable to parse input");
+        let res = get_number_of_splits(&manifold);
+
+        assert_eq!(res, 21);
+    }
+
+    #[rstest]
+    fn test_p1_full_input(puzzle_input: Vec<String>) {
+        let manifold = Day07::parse(&puzzle_input).expect("unable to parse input");
+        let res = get_number_of_splits(&manifold);
+
+        assert_eq!(res, 1585);
+    }
+
+    #[rstest]
+    fn test_p2(test_input: Vec<String>) {
+        let manifold = Day07::parse(&test_input).expect("unable to parse input");
+        let res = get_timelines(&manifold);
+
+        assert_eq!(res, 40);
+    }
+
+    #[rstest]
+    fn test_p2_full_input(puzzle_input: Vec<String>) {
+        let manifold = Day07::parse(&puzzle_input).expect("unable to parse input");
+        let res = get_timelines(&manifold);
+
+        assert_eq!(res, 16716444407407);
+    }
+}
@@ -1,35 +1,30 @@
 use std::fmt::Display;
-use std::time::Instant;
 
-use aoc_common::{format_duration, get_input, tracing_init};
+use aoc_common::Solution;
 
-fn main() {
-    tracing_init();
+pub struct Day08;
 
-    let input = get_input("day08.txt");
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Input = Vec<String>;
+    type Parsed = ();
 
-    let start = Instant::now();
-
-    let (r1, r2) = solve(input.as_slice());
-
-    let t = start.elapsed().as_nanos();
-
-    println!("Part 1: {}", r1);
-    println!("Part 2: {}", r2);
-    println!("Duration: {}", format_duration(t));
-}
+    fn parse(_input: &Vec<String>) -> Result<(), String> {
+        Ok(())
+    }
 
-#[tracing::instrument(skip_all)]
-fn solve(_input: &[String]) -> (impl Display, impl Display) {
-    let p1 = 0;
-    let p2 = 0;
+    fn part1(_: &()) -> impl Display {
+        0
+    }
 
-    (p1, p2)
+    fn part2(_: &()) -> impl Display {
+        0
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc_common::parse_test_input;
+    use aoc_common::{get_input, parse_test_input};
     use rstest::{fixture, rstest};
 
     use super::*;
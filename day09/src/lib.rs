@@ -1,61 +1,39 @@
 use std::fmt::Display;
-use std::time::Instant;
 
-use aoc_common::{Point, format_duration, get_input, tracing_init};
+use aoc_common::parse::{self, item};
+use aoc_common::{Point, Solution};
 use geo::algorithm::contains::Contains;
 use geo::{Coord, LineString, Polygon, Rect, coord};
-use itertools::Itertools;
 use tracing::debug;
 
-fn main() {
-    tracing_init();
+pub struct Day09;
 
-    let input = get_input("day09.txt");
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Input = Vec<String>;
+    type Parsed = Vec<Point<usize>>;
 
-    let start = Instant::now();
-
-    let (r1, r2) = solve(input.as_slice());
-
-    let t = start.elapsed().as_nanos();
-
-    println!("Part 1: {}", r1);
-    println!("Part 2: {}", r2);
-    println!("Duration: {}", format_duration(t));
-}
-
-#[tracing::instrument(skip_all)]
-fn solve(input: &[String]) -> (impl Display, impl Display) {
-    let points = parse(input).expect("invalid input");
+    fn parse(input: &Vec<String>) -> Result<Vec<Point<usize>>, String> {
+        parse(input)
+    }
 
-    let p1 = get_biggest_rectangle(&points);
-    let p2 = get_biggest_rectangle_in_grid(&points);
+    fn part1(points: &Vec<Point<usize>>) -> impl Display {
+        get_biggest_rectangle(points)
+    }
 
-    (p1, p2)
+    fn part2(points: &Vec<Point<usize>>) -> impl Display {
+        get_biggest_rectangle_in_grid(points)
+    }
 }
 
 fn get_rectangle_size(p1: &Point<usize>, p2: &Point<usize>) -> usize {
-    ((p1.x as i64 - p2.x as i64).abs() + 1) as usize
-        * ((p1.y as i64 - p2.y as i64).abs() + 1) as usize
+    let d = p1.abs_diff(p2);
+    (d.x + 1) * (d.y + 1)
 }
 
 #[tracing::instrument(skip_all)]
 fn parse(input: &[String]) -> Result<Vec<Point<usize>>, String> {
-    let mut points = Vec::new();
-    for l in input {
-        if let Some((x, y)) = l.split(',').collect_tuple() {
-            let x = x
-                .parse::<usize>()
-                .map_err(|e| format!("invalid entry: {}: {}", l, e))?;
-            let y = y
-                .parse::<usize>()
-                .map_err(|e| format!("invalid entry: {}: {}", l, e))?;
-            points.push(Point::new(x, y));
-        } else {
-            return Err(format!("invalid entry: {}", l));
-        }
-    }
-
-    Ok(points)
+    Ok(parse::lines(input, item())?)
 }
 
 #[tracing::instrument(skip_all)]
@@ -132,7 +110,7 @@ fn get_polygon(points: &[Point<usize>]) -> Polygon {
 
 #[cfg(test)]
 mod tests {
-    use aoc_common::parse_test_input;
+    use aoc_common::{get_input, parse_test_input};
     use rstest::{fixture, rstest};
 
     use super::*;